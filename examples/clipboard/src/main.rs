@@ -1,5 +1,6 @@
 use std::{cell::RefCell, io, rc::Rc};
 
+use futures::StreamExt;
 use ratatui::{
     layout::Alignment,
     style::{Color, Stylize},
@@ -7,7 +8,7 @@ use ratatui::{
     Frame,
 };
 
-use ratzilla::{event::{KeyCode, KeyEvent}, SelectionMode, WebRenderer};
+use ratzilla::{event::{Event, KeyCode, KeyEvent}, SelectionMode, WebRenderer};
 use examples_shared::backend::{BackendType, MultiBackendBuilder};
 use ratzilla::backend::webgl2::WebGl2BackendOptions;
 
@@ -23,13 +24,21 @@ fn main() -> io::Result<()> {
         .build_terminal()?;
 
     let state = Rc::new(App::default());
+    let mut events = terminal.event_stream()?;
     let event_state = Rc::clone(&state);
-    terminal.on_key_event(move |key_event| {
-        let event_state = event_state.clone();
-        wasm_bindgen_futures::spawn_local(
-            async move { event_state.handle_events(key_event).await },
-        );
-    })?;
+    wasm_bindgen_futures::spawn_local(async move {
+        while let Some(event) = events.next().await {
+            match event {
+                Event::Key(key_event) => event_state.handle_events(key_event).await,
+                Event::Paste(pasted) => {
+                    if let Ok(mut text) = event_state.text.try_borrow_mut() {
+                        *text = pasted;
+                    }
+                }
+                _ => {}
+            }
+        }
+    });
 
     let render_state = Rc::clone(&state);
     terminal.draw_web(move |frame| {
@@ -78,12 +87,6 @@ impl App {
             KeyCode::Char('c') if key_event.ctrl => {
                 self.set_clipboard("i like rats").await;
             }
-            KeyCode::Char('v') if key_event.ctrl => {
-                if let Ok(mut text) = self.text.try_borrow_mut() {
-                    let clipboard_text = self.get_clipboard().await;
-                    *text = clipboard_text;
-                }
-            }
             _ => {}
         }
     }
@@ -94,12 +97,4 @@ impl App {
         let promise = nav.write_text(text);
         wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
     }
-
-    async fn get_clipboard(&self) -> String {
-        let window = web_sys::window().unwrap();
-        let nav = window.navigator().clipboard();
-        let promise = nav.read_text();
-        let result = wasm_bindgen_futures::JsFuture::from(promise).await.unwrap();
-        result.as_string().unwrap_or_default()
-    }
 }
@@ -28,12 +28,12 @@ mod theme;
 use std::{cell::RefCell, rc::Rc};
 
 use app::App;
+use examples_shared::backend::{BackendType, MultiBackendBuilder};
 use ratzilla::{
     backend::webgl2::{SelectionMode, WebGl2BackendOptions},
     ratatui::{layout::Rect, TerminalOptions, Viewport},
     WebRenderer,
 };
-use examples_shared::backend::{BackendType, MultiBackendBuilder};
 
 pub use self::{
     colors::{color_from_oklab, RgbSwatch},
@@ -42,20 +42,21 @@ pub use self::{
 
 fn main() -> std::io::Result<()> {
     std::panic::set_hook(Box::new(console_error_panic_hook::hook));
-    
+
     // this size is to match the size of the terminal when running the demo
     // using vhs in a 1280x640 sized window (github social preview size)
     let viewport = Viewport::Fixed(Rect::new(0, 0, 81, 18));
-    
+
     let mut terminal = MultiBackendBuilder::with_fallback(BackendType::Canvas)
-        .webgl2_options(WebGl2BackendOptions::new()
-            .measure_performance(true)
-            .enable_mouse_selection_with_mode(SelectionMode::default())
-            .enable_console_debug_api()
+        .webgl2_options(
+            WebGl2BackendOptions::new()
+                .measure_performance(true)
+                .enable_mouse_selection_with_mode(SelectionMode::default())
+                .enable_console_debug_api(),
         )
         .terminal_options(TerminalOptions { viewport })
         .build_terminal()?;
-    
+
     let app = Rc::new(RefCell::new(App::default()));
     terminal.on_key_event({
         let app = app.clone();
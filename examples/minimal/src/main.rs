@@ -1,4 +1,4 @@
-use std::{cell::RefCell, io, rc::Rc};
+use std::io;
 
 use ratzilla::ratatui::{
     layout::Alignment,
@@ -6,91 +6,84 @@ use ratzilla::ratatui::{
     widgets::{Block, Paragraph},
 };
 
-use ratzilla::{event::KeyCode, event::MouseButton, event::MouseEventKind, SelectionMode, WebRenderer};
+use ratzilla::{event::Event, event::KeyCode, event::MouseButton, event::MouseEventKind, SelectionMode, WebRenderer};
 
 use examples_shared::backend::{BackendType, MultiBackendBuilder};
 use ratzilla::backend::webgl2::WebGl2BackendOptions;
 
 fn main() -> io::Result<()> {
-    let counter = Rc::new(RefCell::new(0));
-    let mouse_position = Rc::new(RefCell::new((0, 0)));
-    let mouse_button = Rc::new(RefCell::new(None::<MouseButton>));
-    let mouse_event_kind = Rc::new(RefCell::new(None::<MouseEventKind>));
+    let mut counter = 0;
+    let mut mouse_position = (0, 0);
+    let mut mouse_button = None::<MouseButton>;
+    let mut mouse_event_kind = None::<MouseEventKind>;
 
-    let mut terminal = MultiBackendBuilder::with_fallback(BackendType::Dom)
+    let terminal = MultiBackendBuilder::with_fallback(BackendType::Dom)
         .webgl2_options(WebGl2BackendOptions::new()
             .enable_console_debug_api()
             .enable_mouse_selection_with_mode(SelectionMode::Block)
         )
         .build_terminal()?;
 
-    terminal.on_key_event({
-        let counter_cloned = counter.clone();
-        move |key_event| {
-            if key_event.code == KeyCode::Char(' ') {
-                let mut counter = counter_cloned.borrow_mut();
-                *counter += 1;
-            }
-        }
-    })?;
-
-    terminal.on_mouse_event({
-        let mouse_position_cloned = mouse_position.clone();
-        let mouse_button_cloned = mouse_button.clone();
-        let mouse_event_kind_cloned = mouse_event_kind.clone();
-        move |mouse_event| {
-            let btn = match mouse_event.kind {
-                MouseEventKind::Moved => None,
-                MouseEventKind::ButtonDown(btn) => Some(btn),
-                MouseEventKind::ButtonUp(btn) => Some(btn),
-                _ => return
-            };
-
-
-            let mut mouse_position = mouse_position_cloned.borrow_mut();
-            *mouse_position = (mouse_event.col, mouse_event.row);
-            let mut mouse_button = mouse_button_cloned.borrow_mut();
-            *mouse_button = btn;
-            let mut mouse_event_kind = mouse_event_kind_cloned.borrow_mut();
-            *mouse_event_kind = Some(mouse_event.kind);
-        }
-    })?;
-
     // Gruvbox bright orange
     const HOVER_BG: Color = Color::Rgb(254, 128, 25);
 
-    terminal.draw_web(move |f| {
-        let counter = counter.borrow();
-        let mouse_position = mouse_position.borrow();
-        let mouse_button = mouse_button.borrow();
-        let mouse_event_kind = mouse_event_kind.borrow();
+    terminal
+        .draw_web_with_events(move |f, events| {
+            for event in events {
+                match event {
+                    Event::Key(key_event) => {
+                        if key_event.code == KeyCode::Char(' ') {
+                            counter += 1;
+                        }
+                    }
+                    Event::Mouse(mouse_event) => {
+                        let btn = match mouse_event.kind {
+                            MouseEventKind::Moved => None,
+                            MouseEventKind::ButtonDown(btn) => Some(btn),
+                            MouseEventKind::ButtonUp(btn) => Some(btn),
+                            MouseEventKind::Drag(btn) => Some(btn),
+                            MouseEventKind::ScrollUp
+                            | MouseEventKind::ScrollDown
+                            | MouseEventKind::ScrollLeft
+                            | MouseEventKind::ScrollRight => None,
+                            _ => continue
+                        };
+
+                        mouse_position = (mouse_event.col, mouse_event.row);
+                        mouse_button = btn;
+                        mouse_event_kind = Some(mouse_event.kind);
+                    }
+                    _ => {}
+                }
+            }
 
-        f.render_widget(
-            Paragraph::new(format!(
-                "Space pressed: {counter}\n\
-                MouseX: {:?}\n\
-                MouseY: {:?}\n\
-                MouseButton: {mouse_button:?}\n\
-                MouseEvent: {mouse_event_kind:?}",
-                mouse_position.0, mouse_position.1
-            ))
-            .alignment(Alignment::Center)
-            .block(
-                Block::bordered()
-                    .title("Ratzilla")
-                    .title_alignment(Alignment::Center)
-                    .border_style(Color::Yellow),
-            ),
-            f.area(),
-        );
+            f.render_widget(
+                Paragraph::new(format!(
+                    "Space pressed: {counter}\n\
+                    MouseX: {:?}\n\
+                    MouseY: {:?}\n\
+                    MouseButton: {mouse_button:?}\n\
+                    MouseEvent: {mouse_event_kind:?}",
+                    mouse_position.0, mouse_position.1
+                ))
+                .alignment(Alignment::Center)
+                .block(
+                    Block::bordered()
+                        .title("Ratzilla")
+                        .title_alignment(Alignment::Center)
+                        .border_style(Color::Yellow),
+                ),
+                f.area(),
+            );
 
-        // Highlight the hovered cell
-        let (col, row) = *mouse_position;
-        let area = f.area();
-        if col < area.width && row < area.height {
-            f.buffer_mut()[(col, row)].set_bg(HOVER_BG);
-        }
-    });
+            // Highlight the hovered cell
+            let (col, row) = mouse_position;
+            let area = f.area();
+            if col < area.width && row < area.height {
+                f.buffer_mut()[(col, row)].set_bg(HOVER_BG);
+            }
+        })?
+        .detach();
 
     Ok(())
 }
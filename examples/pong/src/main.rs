@@ -1,13 +1,13 @@
 use std::{cell::RefCell, rc::Rc};
 
-use ratzilla::{event::KeyCode, utils::set_document_title, widgets::Hyperlink, CursorShape, WebRenderer};
+use ratzilla::{event::{KeyCode, KeyEventKind}, utils::set_document_title, widgets::Hyperlink, CursorShape, WebRenderer};
 
 use ratzilla::ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Color, Style, Stylize},
     symbols::Marker,
     widgets::{
-        canvas::{Canvas, Circle},
+        canvas::{Canvas, Circle, Rectangle},
         Block, Paragraph, Widget,
     },
 };
@@ -21,6 +21,8 @@ struct App {
     pub ball: Circle,
     vx: f64,
     vy: f64,
+    paddle_x: f64,
+    paddle_vx: f64,
 }
 
 impl App {
@@ -35,25 +37,49 @@ impl App {
             },
             vx: 1.0,
             vy: 1.0,
+            paddle_x: 20.0,
+            paddle_vx: 0.0,
         }
     }
 
+    const PADDLE_WIDTH: f64 = 12.0;
+
     fn pong_canvas(&self) -> impl Widget + '_ {
         Canvas::default()
             .marker(Marker::Dot)
             .block(Block::bordered().title("Pong"))
             .paint(|ctx| {
                 ctx.draw(&self.ball);
+                ctx.draw(&Rectangle {
+                    x: self.paddle_x,
+                    y: 2.0,
+                    width: Self::PADDLE_WIDTH,
+                    height: 2.0,
+                    color: Color::Yellow,
+                });
             })
             .x_bounds([0.0, 50.0])
             .y_bounds([0.0, 100.0])
     }
 
     fn update(&mut self) {
+        self.paddle_x = (self.paddle_x + self.paddle_vx).clamp(0.0, 50.0 - Self::PADDLE_WIDTH);
+
         if self.ball.x < 10.0 || self.ball.x > 40.0 {
             self.vx = -self.vx;
         }
-        if self.ball.y < 10.0 || self.ball.y > 100.0 {
+        if self.ball.y < 10.0 && self.vy < 0.0 {
+            let on_paddle =
+                (self.paddle_x..=self.paddle_x + Self::PADDLE_WIDTH).contains(&self.ball.x);
+            if on_paddle {
+                self.vy = -self.vy;
+            } else {
+                // Missed the ball, serve it again from the top
+                self.count = 0;
+                self.ball.y = 90.0;
+            }
+        }
+        if self.ball.y > 100.0 {
             self.vy = -self.vy;
         }
         self.ball.x += self.vx;
@@ -70,17 +96,45 @@ fn main() -> std::io::Result<()> {
             .grid_id("container")
             .enable_hyperlinks()
             .enable_mouse_selection_with_mode(SelectionMode::default())
+            .report_key_release_events(true)
         )
         .canvas_options(CanvasBackendOptions::new()
             .grid_id("container")
+            .report_key_release_events(true)
+        )
+        .dom_options(
+            DomBackendOptions::new(Some("container".into()), CursorShape::SteadyBlock)
+                .report_key_release_events(true),
         )
-        .dom_options(DomBackendOptions::new(Some("container".into()), CursorShape::SteadyBlock))
         .build_terminal()?;
 
     terminal.on_key_event({
         let app_state_cloned = app_state.clone();
         move |event| {
             let mut app_state = app_state_cloned.borrow_mut();
+
+            // The paddle moves while an arrow key is held down, and stops
+            // when the key is released
+            let direction = match event.code {
+                KeyCode::Left => Some(-1.0),
+                KeyCode::Right => Some(1.0),
+                _ => None,
+            };
+            if let Some(direction) = direction {
+                match event.kind {
+                    KeyEventKind::Press => app_state.paddle_vx = direction,
+                    KeyEventKind::Release if app_state.paddle_vx == direction => {
+                        app_state.paddle_vx = 0.0;
+                    }
+                    _ => {}
+                }
+                return;
+            }
+
+            // Holding a key down should not toggle the ball color repeatedly
+            if event.kind != KeyEventKind::Press {
+                return;
+            }
             match event.code {
                 KeyCode::Char('t') => {
                     let _ = set_document_title("RATATUI");
@@ -111,7 +165,7 @@ fn main() -> std::io::Result<()> {
                 .block(
                     Block::bordered()
                         .title_top("Ratzilla".bold())
-                        .title_bottom("Press 't' to change title, enter to change color")
+                        .title_bottom("Press 't' to change title, space to change color, arrows to move")
                         .border_style(Style::default().fg(Color::Yellow).bg(Color::Black)),
                 ),
             left,
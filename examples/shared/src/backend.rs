@@ -2,12 +2,12 @@ use crate::{fps, utils::inject_backend_footer};
 use ratzilla::{
     backend::{canvas::CanvasBackendOptions, dom::DomBackendOptions, webgl2::WebGl2BackendOptions},
     error::Error,
-    event::{KeyEvent, MouseEvent},
+    event::{ImeEvent, KeyEvent, MouseEvent},
     ratatui::{backend::Backend, prelude::backend::ClearType, Terminal, TerminalOptions},
     CanvasBackend, CellSized, DomBackend, WebEventHandler, WebGl2Backend,
 };
 use std::{convert::TryFrom, fmt, io};
-use web_sys::{window, Url};
+use web_sys::{window, Element, Url};
 
 /// Available backend types
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
@@ -231,6 +231,57 @@ impl WebEventHandler for RatzillaBackend {
             RatzillaBackend::WebGl2(backend) => backend.clear_key_events(),
         }
     }
+
+    fn on_ime_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(ImeEvent) + 'static,
+    {
+        match self {
+            RatzillaBackend::Dom(backend) => backend.on_ime_event(callback),
+            RatzillaBackend::Canvas(backend) => backend.on_ime_event(callback),
+            RatzillaBackend::WebGl2(backend) => backend.on_ime_event(callback),
+        }
+    }
+
+    fn clear_ime_events(&mut self) {
+        match self {
+            RatzillaBackend::Dom(backend) => backend.clear_ime_events(),
+            RatzillaBackend::Canvas(backend) => backend.clear_ime_events(),
+            RatzillaBackend::WebGl2(backend) => backend.clear_ime_events(),
+        }
+    }
+
+    fn terminal_element(&self) -> Option<Element> {
+        match self {
+            RatzillaBackend::Dom(backend) => backend.terminal_element(),
+            RatzillaBackend::Canvas(backend) => backend.terminal_element(),
+            RatzillaBackend::WebGl2(backend) => backend.terminal_element(),
+        }
+    }
+
+    fn ime_input_element(&self) -> Option<Element> {
+        match self {
+            RatzillaBackend::Dom(backend) => backend.ime_input_element(),
+            RatzillaBackend::Canvas(backend) => backend.ime_input_element(),
+            RatzillaBackend::WebGl2(backend) => backend.ime_input_element(),
+        }
+    }
+
+    fn handles_document_key_events(&self) -> bool {
+        match self {
+            RatzillaBackend::Dom(backend) => backend.handles_document_key_events(),
+            RatzillaBackend::Canvas(backend) => backend.handles_document_key_events(),
+            RatzillaBackend::WebGl2(backend) => backend.handles_document_key_events(),
+        }
+    }
+
+    fn handles_gesture_events(&self) -> bool {
+        match self {
+            RatzillaBackend::Dom(backend) => backend.handles_gesture_events(),
+            RatzillaBackend::Canvas(backend) => backend.handles_gesture_events(),
+            RatzillaBackend::WebGl2(backend) => backend.handles_gesture_events(),
+        }
+    }
 }
 
 /// Backend wrapper that automatically tracks FPS by recording frames on each flush.
@@ -356,6 +407,33 @@ impl WebEventHandler for FpsTrackingBackend {
     fn clear_key_events(&mut self) {
         self.inner.clear_key_events()
     }
+
+    fn on_ime_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(ImeEvent) + 'static,
+    {
+        self.inner.on_ime_event(callback)
+    }
+
+    fn clear_ime_events(&mut self) {
+        self.inner.clear_ime_events()
+    }
+
+    fn terminal_element(&self) -> Option<Element> {
+        self.inner.terminal_element()
+    }
+
+    fn ime_input_element(&self) -> Option<Element> {
+        self.inner.ime_input_element()
+    }
+
+    fn handles_document_key_events(&self) -> bool {
+        self.inner.handles_document_key_events()
+    }
+
+    fn handles_gesture_events(&self) -> bool {
+        self.inner.handles_gesture_events()
+    }
 }
 
 /// Builder for creating terminals with different backend types and configuration options.
@@ -2,7 +2,7 @@ use std::{cell::RefCell, io, rc::Rc};
 
 use examples_shared::backend::{BackendType, MultiBackendBuilder};
 use ratzilla::{
-    event::{KeyCode, KeyEvent},
+    event::KeyEvent,
     ratatui::{
         prelude::*,
         widgets::{Block, Borders, Paragraph},
@@ -75,34 +75,6 @@ impl<'a> App<'a> {
     fn handle_events(&mut self, key_event: KeyEvent) {
         self.status_text = std::format!("Last key pressed: {key_event:?}");
 
-        if let Some(key) = try_convert_code(key_event.code) {
-            self.textarea.input(tui_textarea::Input {
-                key,
-                ctrl: key_event.ctrl,
-                alt: key_event.alt,
-                shift: key_event.shift,
-            });
-        }
-    }
-}
-
-fn try_convert_code(code: KeyCode) -> Option<tui_textarea::Key> {
-    match code {
-        KeyCode::Char(c) => Some(tui_textarea::Key::Char(c)),
-        KeyCode::F(n) => Some(tui_textarea::Key::F(n)),
-        KeyCode::Backspace => Some(tui_textarea::Key::Backspace),
-        KeyCode::Enter => Some(tui_textarea::Key::Enter),
-        KeyCode::Left => Some(tui_textarea::Key::Left),
-        KeyCode::Right => Some(tui_textarea::Key::Right),
-        KeyCode::Up => Some(tui_textarea::Key::Up),
-        KeyCode::Down => Some(tui_textarea::Key::Down),
-        KeyCode::Tab => Some(tui_textarea::Key::Tab),
-        KeyCode::Delete => Some(tui_textarea::Key::Delete),
-        KeyCode::Home => Some(tui_textarea::Key::Home),
-        KeyCode::End => Some(tui_textarea::Key::End),
-        KeyCode::PageUp => Some(tui_textarea::Key::PageUp),
-        KeyCode::PageDown => Some(tui_textarea::Key::PageDown),
-        KeyCode::Esc => Some(tui_textarea::Key::Esc),
-        KeyCode::Unidentified => None,
+        self.textarea.input(key_event);
     }
 }
@@ -1,7 +1,7 @@
 use std::{cell::RefCell, io, rc::Rc};
 
 use ratzilla::backend::cursor::CursorShape;
-use ratzilla::event::KeyEvent;
+use ratzilla::event::{ImeEvent, KeyEvent};
 use ratzilla::ratatui::layout::{Constraint, Layout, Position};
 use ratzilla::ratatui::style::{Modifier, Style, Stylize};
 use ratzilla::ratatui::text::{Line, Span, Text};
@@ -17,10 +17,11 @@ use ratzilla::backend::dom::DomBackendOptions;
 use ratzilla::backend::webgl2::{SelectionMode, WebGl2BackendOptions};
 
 fn main() -> io::Result<()> {
-    let dom_options = DomBackendOptions::new(None, CursorShape::SteadyUnderScore);
+    let dom_options = DomBackendOptions::new(None, CursorShape::SteadyUnderScore).autofocus(true);
 
     let webgl2_options = WebGl2BackendOptions::new()
         .cursor_shape(CursorShape::SteadyUnderScore)
+        .autofocus(true)
         .enable_console_debug_api()
         .enable_mouse_selection_with_mode(SelectionMode::default());
 
@@ -39,6 +40,14 @@ fn main() -> io::Result<()> {
         }
     })?;
 
+    terminal.on_ime_event({
+        let event_state = app.clone();
+        move |ime_event| {
+            let mut state = event_state.borrow_mut();
+            state.handle_ime_event(ime_event);
+        }
+    })?;
+
     terminal.draw_web({
         let render_state = app.clone();
         move |frame| {
@@ -54,6 +63,8 @@ fn main() -> io::Result<()> {
 struct App {
     /// Current value of the input box
     input: String,
+    /// Text being composed with an input method, not yet part of the input
+    preedit: String,
     /// Position of cursor in the editor area.
     character_index: usize,
     /// Current input mode
@@ -71,6 +82,7 @@ impl App {
     const fn new() -> Self {
         Self {
             input: String::new(),
+            preedit: String::new(),
             input_mode: InputMode::Normal,
             messages: Vec::new(),
             character_index: 0,
@@ -160,6 +172,19 @@ impl App {
         }
     }
 
+    fn handle_ime_event(&mut self, ime_event: ImeEvent) {
+        if let InputMode::Normal = self.input_mode {
+            return;
+        }
+        match ime_event {
+            ImeEvent::Composition { preedit, .. } => self.preedit = preedit,
+            ImeEvent::Commit(text) => {
+                self.preedit.clear();
+                text.chars().for_each(|c| self.enter_char(c));
+            }
+        }
+    }
+
     fn draw(&self, frame: &mut Frame) {
         let vertical = Layout::vertical([
             Constraint::Length(1),
@@ -188,7 +213,12 @@ impl App {
         let help_message = Paragraph::new(text);
         frame.render_widget(help_message, help_area);
 
-        let input = Paragraph::new(self.input.as_str())
+        let (before_cursor, after_cursor) = self.input.split_at(self.byte_index());
+        let input = Paragraph::new(Line::from(vec![
+            Span::raw(before_cursor),
+            Span::raw(self.preedit.as_str()).underlined(),
+            Span::raw(after_cursor),
+        ]))
             .style(match self.input_mode {
                 InputMode::Normal => Style::default(),
                 InputMode::Editing => Style::default().fg(Color::Yellow),
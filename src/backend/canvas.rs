@@ -7,12 +7,19 @@ use crate::{
         cell_sized::CellSized,
         color::{actual_bg_color, actual_fg_color},
         event_callback::{
-            create_mouse_event, EventCallback, MouseConfig, KEY_EVENT_TYPES, MOUSE_EVENT_TYPES,
+            blur_element, create_focus_callback, create_key_callback, create_mouse_callback,
+            create_paste_callback, element_has_focus, focus_element, EventCallback, MouseConfig,
+            ResizeCallback, SharedMouseConfig, MOUSE_EVENT_TYPES,
         },
+        gamepad::{GamepadCallback, DEFAULT_GAMEPAD_DEAD_ZONE},
+        ime::{ImeInput, TerminalElements},
+        key_capture::KeyCapture,
+        mouse_cursor::{MouseCursorCallback, MouseCursors},
+        touch::create_gesture_callback,
         utils::*,
     },
     error::Error,
-    event::{KeyEvent, MouseEvent},
+    event::{FocusEvent, GamepadEvent, GestureEvent, ImeEvent, KeyEvent, MouseEvent, ResizeEvent},
     render::WebEventHandler,
     CursorShape,
 };
@@ -52,6 +59,22 @@ pub struct CanvasBackendOptions {
     /// this option may cause some performance issues when dealing with large
     /// numbers of simultaneous changes.
     always_clip_cells: bool,
+    /// Prevent the page from scrolling on mouse wheel events.
+    prevent_default_scroll: bool,
+    /// Prevent the page from panning and zooming on touches.
+    prevent_default_touch: bool,
+    /// Report key release events.
+    report_key_release_events: bool,
+    /// Keys whose default browser action is prevented.
+    key_capture: KeyCapture,
+    /// Listen for key events on the document instead of the terminal.
+    document_key_events: bool,
+    /// Report touch gestures to the unified event handler.
+    gesture_events: bool,
+    /// The dead zone of gamepad axes.
+    gamepad_dead_zone: Option<f64>,
+    /// Focus the terminal when the backend is created.
+    autofocus: bool,
 }
 
 impl CanvasBackendOptions {
@@ -71,6 +94,86 @@ impl CanvasBackendOptions {
         self.size = Some(size);
         self
     }
+
+    /// Sets whether mouse wheel events over the canvas should be prevented
+    /// from scrolling the page.
+    ///
+    /// Scroll events are still delivered to [`WebEventHandler::on_mouse_event`].
+    pub fn prevent_default_scroll(mut self, prevent: bool) -> Self {
+        self.prevent_default_scroll = prevent;
+        self
+    }
+
+    /// Sets whether touches on the canvas should be prevented from panning
+    /// and zooming the page.
+    ///
+    /// This lets dragging a finger over the canvas be reported as a mouse
+    /// drag to [`WebEventHandler::on_mouse_event`], instead of the browser
+    /// taking over the touch to scroll the page.
+    pub fn prevent_default_touch(mut self, prevent: bool) -> Self {
+        self.prevent_default_touch = prevent;
+        self
+    }
+
+    /// Sets whether key releases are reported as
+    /// [`KeyEventKind::Release`](crate::event::KeyEventKind::Release)
+    /// events in addition to presses and repeats.
+    ///
+    /// This is disabled by default so that handlers which don't check
+    /// [`KeyEvent::kind`](crate::event::KeyEvent::kind) aren't called twice
+    /// for every key stroke.
+    pub fn report_key_release_events(mut self, report: bool) -> Self {
+        self.report_key_release_events = report;
+        self
+    }
+
+    /// Sets which keys have their default browser action prevented, e.g. so
+    /// that Tab doesn't move the focus away from the terminal.
+    ///
+    /// No keys are captured by default. See [`KeyCapture`] for details.
+    pub fn key_capture(mut self, capture: KeyCapture) -> Self {
+        self.key_capture = capture;
+        self
+    }
+
+    /// Sets whether key events are listened for on the whole document rather
+    /// than on the terminal, so that they are received without the terminal
+    /// having focus.
+    ///
+    /// This is useful for applications that take up the whole page.
+    pub fn document_key_events(mut self, enabled: bool) -> Self {
+        self.document_key_events = enabled;
+        self
+    }
+
+    /// Sets whether touch gestures are reported to
+    /// [`WebEventHandler::on_event`].
+    ///
+    /// This is disabled by default, as recognizing gestures stops the browser
+    /// from panning and zooming the page on touches over the terminal.
+    /// Handlers set with [`WebEventHandler::on_gesture_event`] always receive
+    /// gestures.
+    pub fn gesture_events(mut self, enabled: bool) -> Self {
+        self.gesture_events = enabled;
+        self
+    }
+
+    /// Sets the dead zone of gamepad axes, between `0.0` and `1.0`.
+    ///
+    /// Axis values closer to the center than this are reported as `0.0`, so
+    /// that sticks that don't return exactly to the center aren't reported
+    /// as moved. Defaults to `0.1`.
+    pub fn gamepad_dead_zone(mut self, dead_zone: f64) -> Self {
+        self.gamepad_dead_zone = Some(dead_zone);
+        self
+    }
+
+    /// Sets whether the terminal should receive keyboard focus when the backend is created, so
+    /// that key events are delivered without clicking it first.
+    pub fn autofocus(mut self, autofocus: bool) -> Self {
+        self.autofocus = autofocus;
+        self
+    }
 }
 
 /// Canvas renderer.
@@ -142,10 +245,43 @@ pub struct CanvasBackend {
     cursor_shape: CursorShape,
     /// Draw cell boundaries with specified color.
     debug_mode: Option<String>,
+    /// Prevent the page from scrolling on mouse wheel events.
+    prevent_default_scroll: bool,
+    /// Prevent the page from panning and zooming on touches.
+    prevent_default_touch: bool,
+    /// Report key release events.
+    report_key_release_events: bool,
+    /// Keys whose default browser action is prevented.
+    key_capture: KeyCapture,
+    /// Listen for key events on the document instead of the canvas.
+    document_key_events: bool,
+    /// Report touch gestures to the unified event handler.
+    gesture_events: bool,
+    /// The dead zone of gamepad axes.
+    gamepad_dead_zone: f64,
     /// Mouse event callback handler.
     mouse_callback: Option<MouseCallbackState>,
+    /// Gesture event callback handler.
+    gesture_callback: Option<EventCallback<web_sys::PointerEvent>>,
     /// Key event callback handler.
     key_callback: Option<EventCallback<web_sys::KeyboardEvent>>,
+    /// Paste event callback handler.
+    paste_callback: Option<EventCallback<web_sys::ClipboardEvent>>,
+    /// Focus event callback handler.
+    focus_callback: Option<EventCallback<web_sys::FocusEvent>>,
+    /// Resize callback handler.
+    resize_callback: Option<ResizeCallback>,
+    /// The canvas element and its IME input.
+    elements: TerminalElements,
+    /// IME composition input.
+    ime_input: Option<ImeInput>,
+    /// Gamepad event callback handler.
+    gamepad_callback: Option<GamepadCallback>,
+    /// Mouse cursor regions.
+    mouse_cursor_callback: Option<MouseCursorCallback>,
+    /// The coordinate translation shared with the mouse and gesture event
+    /// handlers.
+    shared_mouse_config: SharedMouseConfig,
 }
 
 /// Type alias for mouse event callback state.
@@ -176,9 +312,13 @@ impl CanvasBackend {
             .unwrap_or_else(|| (parent.client_width() as u32, parent.client_height() as u32));
 
         let canvas = Canvas::new(parent, width, height, Color::Black)?;
+        if options.autofocus {
+            focus_element(&canvas.inner)?;
+        }
+        let elements = TerminalElements::new(canvas.inner.clone().into());
         let buffer = get_sized_buffer_from_canvas(&canvas.inner);
         let changed_cells = bitvec![0; buffer.len() * buffer[0].len()];
-        Ok(Self {
+        let backend = Self {
             prev_buffer: buffer.clone(),
             always_clip_cells: options.always_clip_cells,
             buffer,
@@ -188,9 +328,72 @@ impl CanvasBackend {
             cursor_position: None,
             cursor_shape: CursorShape::SteadyBlock,
             debug_mode: None,
+            prevent_default_scroll: options.prevent_default_scroll,
+            prevent_default_touch: options.prevent_default_touch,
+            report_key_release_events: options.report_key_release_events,
+            key_capture: options.key_capture,
+            document_key_events: options.document_key_events,
+            gesture_events: options.gesture_events,
+            gamepad_dead_zone: options
+                .gamepad_dead_zone
+                .unwrap_or(DEFAULT_GAMEPAD_DEAD_ZONE),
             mouse_callback: None,
+            gesture_callback: None,
             key_callback: None,
-        })
+            paste_callback: None,
+            focus_callback: None,
+            resize_callback: None,
+            elements,
+            ime_input: None,
+            gamepad_callback: None,
+            mouse_cursor_callback: None,
+            shared_mouse_config: SharedMouseConfig::default(),
+        };
+        backend.update_mouse_config();
+        Ok(backend)
+    }
+
+    /// Returns a handle for setting the mouse cursor over regions of the
+    /// terminal.
+    ///
+    /// See [`MouseCursors`] for details.
+    pub fn mouse_cursors(&mut self) -> Result<MouseCursors, Error> {
+        if let Some(callback) = &self.mouse_cursor_callback {
+            return Ok(callback.cursors());
+        }
+        let callback = MouseCursorCallback::new(
+            self.canvas.inner.clone().into(),
+            self.mouse_config(),
+            MouseCursors::default(),
+        )?;
+        let cursors = callback.cursors();
+        self.mouse_cursor_callback = Some(callback);
+        Ok(cursors)
+    }
+
+    /// Returns the element the canvas, and its IME input, are placed in.
+    fn container(&self) -> Result<web_sys::Element, Error> {
+        self.canvas
+            .inner
+            .parent_element()
+            .ok_or(Error::UnableToRetrieveComponent("canvas parent"))
+    }
+
+    /// Returns the coordinate translation for mouse events.
+    fn mouse_config(&self) -> MouseConfig {
+        let grid_width = self.buffer[0].len() as u16;
+        let grid_height = self.buffer.len() as u16;
+        MouseConfig::new(grid_width, grid_height)
+            .with_offset(5.0) // Canvas translation offset
+            .with_cell_dimensions(CELL_WIDTH, CELL_HEIGHT)
+            .with_prevent_default_scroll(self.prevent_default_scroll)
+            .with_prevent_default_touch(self.prevent_default_touch)
+    }
+
+    /// Updates the coordinate translation used by the mouse and gesture event
+    /// handlers to the current grid size.
+    fn update_mouse_config(&self) {
+        *self.shared_mouse_config.borrow_mut() = self.mouse_config();
     }
 
     /// Sets the background color of the canvas.
@@ -485,6 +688,11 @@ impl Backend for CanvasBackend {
     /// This function is called after the [`CanvasBackend::draw`] function to
     /// actually render the content to the screen.
     fn flush(&mut self) -> IoResult<()> {
+        self.update_mouse_config();
+        if let Some(callback) = &self.mouse_cursor_callback {
+            callback.refresh(self.mouse_config());
+        }
+
         // Only runs once.
         if !self.initialized {
             self.update_grid(true)?;
@@ -499,6 +707,12 @@ impl Backend for CanvasBackend {
 
         self.prev_buffer = self.buffer.clone();
 
+        if self.resize_callback.is_some() {
+            let size = self.window_size()?.into();
+            if let Some(resize_callback) = &mut self.resize_callback {
+                resize_callback.update(size);
+            }
+        }
         Ok(())
     }
 
@@ -541,7 +755,13 @@ impl Backend for CanvasBackend {
     }
 
     fn window_size(&mut self) -> IoResult<WindowSize> {
-        unimplemented!()
+        Ok(WindowSize {
+            columns_rows: self.size()?,
+            pixels: Size::new(
+                self.canvas.inner.width() as u16,
+                self.canvas.inner.height() as u16,
+            ),
+        })
     }
 
     fn get_cursor_position(&mut self) -> IoResult<Position> {
@@ -563,6 +783,9 @@ impl Backend for CanvasBackend {
             }
         }
         self.cursor_position = Some(new_pos);
+        if let Some(ime_input) = &self.ime_input {
+            ime_input.set_cursor_position(new_pos, (CELL_WIDTH, CELL_HEIGHT));
+        }
         Ok(())
     }
 
@@ -575,33 +798,21 @@ impl Backend for CanvasBackend {
 }
 
 impl WebEventHandler for CanvasBackend {
-    fn on_mouse_event<F>(&mut self, mut callback: F) -> Result<(), Error>
+    fn on_mouse_event<F>(&mut self, callback: F) -> Result<(), Error>
     where
         F: FnMut(MouseEvent) + 'static,
     {
         // Clear any existing handlers first
         self.clear_mouse_events();
 
-        // Get grid dimensions from the buffer
-        let grid_width = self.buffer[0].len() as u16;
-        let grid_height = self.buffer.len() as u16;
-
-        // Configure coordinate translation for canvas backend
-        let config = MouseConfig::new(grid_width, grid_height)
-            .with_offset(5.0) // Canvas translation offset
-            .with_cell_dimensions(CELL_WIDTH, CELL_HEIGHT);
-
         let element: web_sys::Element = self.canvas.inner.clone().into();
-        let element_for_closure = element.clone();
 
         // Create mouse event callback
-        let mouse_callback = EventCallback::new(
+        let mouse_callback = create_mouse_callback(
             element,
             MOUSE_EVENT_TYPES,
-            move |event: web_sys::MouseEvent| {
-                let mouse_event = create_mouse_event(&event, &element_for_closure, &config);
-                callback(mouse_event);
-            },
+            self.shared_mouse_config.clone(),
+            callback,
         )?;
 
         self.mouse_callback = Some(mouse_callback);
@@ -614,14 +825,33 @@ impl WebEventHandler for CanvasBackend {
         self.mouse_callback = None;
     }
 
-    fn on_key_event<F>(&mut self, mut callback: F) -> Result<(), Error>
+    fn on_gesture_event<F>(&mut self, callback: F) -> Result<(), Error>
     where
-        F: FnMut(KeyEvent) + 'static,
+        F: FnMut(GestureEvent) + 'static,
     {
         // Clear any existing handlers first
-        self.clear_key_events();
+        self.clear_gesture_events();
 
         let element: web_sys::Element = self.canvas.inner.clone().into();
+        self.gesture_callback = Some(create_gesture_callback(
+            element,
+            self.shared_mouse_config.clone(),
+            callback,
+        )?);
+
+        Ok(())
+    }
+
+    fn clear_gesture_events(&mut self) {
+        self.gesture_callback = None;
+    }
+
+    fn on_key_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(KeyEvent) + 'static,
+    {
+        // Clear any existing handlers first
+        self.clear_key_events();
 
         // Make the canvas focusable so it can receive key events
         self.canvas
@@ -629,12 +859,13 @@ impl WebEventHandler for CanvasBackend {
             .set_attribute("tabindex", "0")
             .map_err(Error::from)?;
 
-        self.key_callback = Some(EventCallback::new(
-            element,
-            KEY_EVENT_TYPES,
-            move |event: web_sys::KeyboardEvent| {
-                callback(event.into());
-            },
+        self.key_callback = Some(create_key_callback(
+            self.canvas.inner.clone().into(),
+            self.elements.clone(),
+            self.report_key_release_events,
+            self.key_capture.clone(),
+            self.document_key_events,
+            callback,
         )?);
 
         Ok(())
@@ -643,6 +874,135 @@ impl WebEventHandler for CanvasBackend {
     fn clear_key_events(&mut self) {
         self.key_callback = None;
     }
+
+    fn on_paste_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(String) + 'static,
+    {
+        // Clear any existing handlers first
+        self.clear_paste_events();
+
+        let element: web_sys::Element = self.canvas.inner.clone().into();
+
+        // Make the canvas focusable so it can receive paste events
+        element.set_attribute("tabindex", "0")?;
+
+        self.paste_callback = Some(create_paste_callback(
+            element,
+            self.elements.clone(),
+            callback,
+        )?);
+
+        Ok(())
+    }
+
+    fn clear_paste_events(&mut self) {
+        self.paste_callback = None;
+    }
+
+    fn on_focus_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(FocusEvent) + 'static,
+    {
+        // Clear any existing handlers first
+        self.clear_focus_events();
+
+        self.focus_callback = Some(create_focus_callback(
+            self.container()?,
+            self.elements.clone(),
+            callback,
+        )?);
+
+        Ok(())
+    }
+
+    fn clear_focus_events(&mut self) {
+        self.focus_callback = None;
+    }
+
+    fn on_resize<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(ResizeEvent) + 'static,
+    {
+        let size = self.window_size().ok().map(Into::into);
+        self.resize_callback = Some(ResizeCallback::new(size, callback));
+        Ok(())
+    }
+
+    fn clear_resize_events(&mut self) {
+        self.resize_callback = None;
+    }
+
+    fn focus(&self) -> Result<(), Error> {
+        focus_element(&self.canvas.inner)
+    }
+
+    fn blur(&self) -> Result<(), Error> {
+        blur_element(&self.elements)
+    }
+
+    fn has_focus(&self) -> bool {
+        element_has_focus(&self.elements)
+    }
+
+    fn on_ime_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(ImeEvent) + 'static,
+    {
+        // Clear any existing handlers first
+        self.clear_ime_events();
+
+        let element: web_sys::Element = self.canvas.inner.clone().into();
+
+        // Make the canvas focusable so it can hand focus to the IME input
+        element.set_attribute("tabindex", "0")?;
+
+        self.ime_input = Some(ImeInput::new(
+            &self.elements,
+            &self.container()?,
+            (CELL_WIDTH, CELL_HEIGHT),
+            5.0, // Canvas translation offset
+            callback,
+        )?);
+
+        Ok(())
+    }
+
+    fn clear_ime_events(&mut self) {
+        self.ime_input = None;
+    }
+
+    fn on_gamepad_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(GamepadEvent) + 'static,
+    {
+        self.gamepad_callback = Some(GamepadCallback::new(self.gamepad_dead_zone, callback));
+        Ok(())
+    }
+
+    fn clear_gamepad_events(&mut self) {
+        self.gamepad_callback = None;
+    }
+
+    fn poll_gamepads(&mut self) -> Option<bool> {
+        self.gamepad_callback.as_mut().map(GamepadCallback::poll)
+    }
+
+    fn ime_input_element(&self) -> Option<web_sys::Element> {
+        self.ime_input.as_ref().map(ImeInput::textarea)
+    }
+
+    fn handles_document_key_events(&self) -> bool {
+        self.document_key_events
+    }
+
+    fn handles_gesture_events(&self) -> bool {
+        self.gesture_events
+    }
+
+    fn terminal_element(&self) -> Option<web_sys::Element> {
+        Some(self.canvas.inner.clone().into())
+    }
 }
 
 /// Optimizes canvas rendering by batching adjacent cells with the same color into a single rectangle.
@@ -691,3 +1051,18 @@ impl RowColorOptimizer {
         self.pending_region.take()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    use crate::backend::event_callback::mouse_event_matrix;
+
+    #[wasm_bindgen_test]
+    fn test_mouse_event_matrix() {
+        let mut backend = CanvasBackend::new().expect("failed to create backend");
+        let target: web_sys::Element = backend.canvas.inner.clone().into();
+        mouse_event_matrix::check(&mut backend, &target);
+    }
+}
@@ -18,12 +18,19 @@ use crate::{
     backend::{
         cell_sized::CellSized,
         event_callback::{
-            create_mouse_event, EventCallback, MouseConfig, KEY_EVENT_TYPES, MOUSE_EVENT_TYPES,
+            blur_element, create_focus_callback, create_key_callback, create_mouse_callback,
+            create_paste_callback, element_has_focus, focus_element, EventCallback, MouseConfig,
+            ResizeCallback, SharedMouseConfig, MOUSE_EVENT_TYPES,
         },
+        gamepad::{GamepadCallback, DEFAULT_GAMEPAD_DEAD_ZONE},
+        ime::{ImeInput, TerminalElements},
+        key_capture::KeyCapture,
+        mouse_cursor::{MouseCursorCallback, MouseCursors},
+        touch::create_gesture_callback,
         utils::*,
     },
     error::Error,
-    event::{KeyEvent, MouseEvent},
+    event::{FocusEvent, GamepadEvent, GestureEvent, ImeEvent, KeyEvent, MouseEvent, ResizeEvent},
     render::WebEventHandler,
     CursorShape,
 };
@@ -38,6 +45,22 @@ pub struct DomBackendOptions {
     grid_id: Option<String>,
     /// The cursor shape.
     cursor_shape: CursorShape,
+    /// Prevent the page from scrolling on mouse wheel events.
+    prevent_default_scroll: bool,
+    /// Prevent the page from panning and zooming on touches.
+    prevent_default_touch: bool,
+    /// Report key release events.
+    report_key_release_events: bool,
+    /// Keys whose default browser action is prevented.
+    key_capture: KeyCapture,
+    /// Listen for key events on the document instead of the terminal.
+    document_key_events: bool,
+    /// Report touch gestures to the unified event handler.
+    gesture_events: bool,
+    /// The dead zone of gamepad axes.
+    gamepad_dead_zone: Option<f64>,
+    /// Focus the terminal when the backend is created.
+    autofocus: bool,
 }
 
 impl DomBackendOptions {
@@ -46,9 +69,90 @@ impl DomBackendOptions {
         Self {
             grid_id,
             cursor_shape,
+            ..Default::default()
         }
     }
 
+    /// Sets whether mouse wheel events over the grid should be prevented from
+    /// scrolling the page.
+    ///
+    /// Scroll events are still delivered to [`WebEventHandler::on_mouse_event`].
+    pub fn prevent_default_scroll(mut self, prevent: bool) -> Self {
+        self.prevent_default_scroll = prevent;
+        self
+    }
+
+    /// Sets whether touches on the grid should be prevented from panning
+    /// and zooming the page.
+    ///
+    /// This lets dragging a finger over the grid be reported as a mouse
+    /// drag to [`WebEventHandler::on_mouse_event`], instead of the browser
+    /// taking over the touch to scroll the page.
+    pub fn prevent_default_touch(mut self, prevent: bool) -> Self {
+        self.prevent_default_touch = prevent;
+        self
+    }
+
+    /// Sets whether key releases are reported as
+    /// [`KeyEventKind::Release`](crate::event::KeyEventKind::Release)
+    /// events in addition to presses and repeats.
+    ///
+    /// This is disabled by default so that handlers which don't check
+    /// [`KeyEvent::kind`](crate::event::KeyEvent::kind) aren't called twice
+    /// for every key stroke.
+    pub fn report_key_release_events(mut self, report: bool) -> Self {
+        self.report_key_release_events = report;
+        self
+    }
+
+    /// Sets which keys have their default browser action prevented, e.g. so
+    /// that Tab doesn't move the focus away from the terminal.
+    ///
+    /// No keys are captured by default. See [`KeyCapture`] for details.
+    pub fn key_capture(mut self, capture: KeyCapture) -> Self {
+        self.key_capture = capture;
+        self
+    }
+
+    /// Sets whether key events are listened for on the whole document rather
+    /// than on the terminal, so that they are received without the terminal
+    /// having focus.
+    ///
+    /// This is useful for applications that take up the whole page.
+    pub fn document_key_events(mut self, enabled: bool) -> Self {
+        self.document_key_events = enabled;
+        self
+    }
+
+    /// Sets whether touch gestures are reported to
+    /// [`WebEventHandler::on_event`].
+    ///
+    /// This is disabled by default, as recognizing gestures stops the browser
+    /// from panning and zooming the page on touches over the terminal.
+    /// Handlers set with [`WebEventHandler::on_gesture_event`] always receive
+    /// gestures.
+    pub fn gesture_events(mut self, enabled: bool) -> Self {
+        self.gesture_events = enabled;
+        self
+    }
+
+    /// Sets the dead zone of gamepad axes, between `0.0` and `1.0`.
+    ///
+    /// Axis values closer to the center than this are reported as `0.0`, so
+    /// that sticks that don't return exactly to the center aren't reported
+    /// as moved. Defaults to `0.1`.
+    pub fn gamepad_dead_zone(mut self, dead_zone: f64) -> Self {
+        self.gamepad_dead_zone = Some(dead_zone);
+        self
+    }
+
+    /// Sets whether the terminal should receive keyboard focus once the grid is first drawn, so
+    /// that key events are delivered without clicking it first.
+    pub fn autofocus(mut self, autofocus: bool) -> Self {
+        self.autofocus = autofocus;
+        self
+    }
+
     /// Returns the grid ID.
     ///
     /// - If the grid ID is not set, it returns `"grid"`.
@@ -82,6 +186,8 @@ pub struct DomBackend {
     grid: Element,
     /// The parent of the grid element.
     grid_parent: Element,
+    /// The grid element and its IME input.
+    elements: TerminalElements,
     /// Document.
     document: Document,
     /// Options.
@@ -98,8 +204,25 @@ pub struct DomBackend {
     _resize_callback: EventCallback<web_sys::Event>,
     /// Mouse event callback handler.
     mouse_callback: Option<DomMouseCallbackState>,
+    /// Gesture event callback handler.
+    gesture_callback: Option<EventCallback<web_sys::PointerEvent>>,
     /// Key event callback handler.
     key_callback: Option<EventCallback<web_sys::KeyboardEvent>>,
+    /// Paste event callback handler.
+    paste_callback: Option<EventCallback<web_sys::ClipboardEvent>>,
+    /// Focus event callback handler.
+    focus_callback: Option<EventCallback<web_sys::FocusEvent>>,
+    /// Resize callback handler.
+    resize_callback: Option<ResizeCallback>,
+    /// IME composition input.
+    ime_input: Option<ImeInput>,
+    /// Gamepad event callback handler.
+    gamepad_callback: Option<GamepadCallback>,
+    /// Mouse cursor regions.
+    mouse_cursor_callback: Option<MouseCursorCallback>,
+    /// The coordinate translation shared with the mouse and gesture event
+    /// handlers.
+    shared_mouse_config: SharedMouseConfig,
 }
 
 /// Type alias for mouse event callback state.
@@ -115,7 +238,17 @@ impl std::fmt::Debug for DomBackend {
             .field("cursor_position", &self.cursor_position)
             .field("resize_callback", &"...")
             .field("mouse_callback", &self.mouse_callback.is_some())
+            .field("gesture_callback", &self.gesture_callback.is_some())
             .field("key_callback", &self.key_callback.is_some())
+            .field("paste_callback", &self.paste_callback.is_some())
+            .field("focus_callback", &self.focus_callback.is_some())
+            .field("resize_callback", &self.resize_callback.is_some())
+            .field("ime_input", &self.ime_input.is_some())
+            .field("gamepad_callback", &self.gamepad_callback.is_some())
+            .field(
+                "mouse_cursor_callback",
+                &self.mouse_cursor_callback.is_some(),
+            )
             .finish()
     }
 }
@@ -159,10 +292,12 @@ impl DomBackend {
             },
         )?;
 
+        let grid = document.create_element("div")?;
         let mut backend = Self {
             initialized,
             cells: vec![],
-            grid: document.create_element("div")?,
+            elements: TerminalElements::new(grid.clone()),
+            grid,
             grid_parent,
             options,
             document,
@@ -172,12 +307,87 @@ impl DomBackend {
             cell_size,
             _resize_callback: resize_callback,
             mouse_callback: None,
+            gesture_callback: None,
             key_callback: None,
+            paste_callback: None,
+            focus_callback: None,
+            resize_callback: None,
+            ime_input: None,
+            gamepad_callback: None,
+            mouse_cursor_callback: None,
+            shared_mouse_config: SharedMouseConfig::default(),
         };
         backend.reset_grid()?;
+        backend.update_mouse_config();
         Ok(backend)
     }
 
+    /// Returns a handle for setting the mouse cursor over regions of the
+    /// terminal.
+    ///
+    /// See [`MouseCursors`] for details.
+    pub fn mouse_cursors(&mut self) -> Result<MouseCursors, Error> {
+        if let Some(callback) = &self.mouse_cursor_callback {
+            return Ok(callback.cursors());
+        }
+        let callback = MouseCursorCallback::new(
+            self.grid.clone(),
+            self.mouse_config(),
+            MouseCursors::default(),
+        )?;
+        let cursors = callback.cursors();
+        self.mouse_cursor_callback = Some(callback);
+        Ok(cursors)
+    }
+
+    /// Returns the coordinate translation for mouse events.
+    ///
+    /// The measured cell size is used, so that coordinates are also correct
+    /// for events on the grid parent, which may be larger than the grid.
+    fn mouse_config(&self) -> MouseConfig {
+        MouseConfig::new(self.size.width, self.size.height)
+            .with_cell_dimensions(self.cell_size.0, self.cell_size.1)
+            .with_prevent_default_scroll(self.options.prevent_default_scroll)
+            .with_prevent_default_touch(self.options.prevent_default_touch)
+    }
+
+    /// Updates the coordinate translation used by the mouse and gesture event
+    /// handlers to the current grid and cell size.
+    fn update_mouse_config(&self) {
+        *self.shared_mouse_config.borrow_mut() = self.mouse_config();
+    }
+
+    /// Applies the mouse cursor regions, moving them to the new grid element
+    /// after a resize.
+    fn update_mouse_cursor(&mut self) -> Result<(), Error> {
+        let Some(callback) = &self.mouse_cursor_callback else {
+            return Ok(());
+        };
+        if callback.element() == &self.grid {
+            callback.refresh(self.mouse_config());
+        } else {
+            let cursors = callback.cursors();
+            self.mouse_cursor_callback = Some(MouseCursorCallback::new(
+                self.grid.clone(),
+                self.mouse_config(),
+                cursors,
+            )?);
+        }
+        Ok(())
+    }
+
+    /// Moves the IME input to the new grid element after a resize.
+    fn update_ime_input(&mut self) -> Result<(), Error> {
+        let Some(ime_input) = &self.ime_input else {
+            return Ok(());
+        };
+        if ime_input.element() != &self.grid {
+            self.grid.set_attribute("tabindex", "0")?;
+            self.ime_input = Some(ime_input.rebind(&self.grid_parent)?);
+        }
+        Ok(())
+    }
+
     /// Measures the pixel dimensions of a single terminal cell.
     ///
     /// Creates a temporary `<pre><span>` probe element that inherits the
@@ -229,9 +439,16 @@ impl DomBackend {
     const RESIZE_EVENT_TYPES: &[&str] = &["resize"];
 
     /// Reset the grid and clear the cells.
+    ///
+    /// The new grid is focusable if the previous one was.
     fn reset_grid(&mut self) -> Result<(), Error> {
+        let tabindex = self.grid.get_attribute("tabindex");
         self.grid = self.document.create_element("div")?;
         self.grid.set_attribute("id", &self.options.grid_id())?;
+        if let Some(tabindex) = tabindex {
+            self.grid.set_attribute("tabindex", &tabindex)?;
+        }
+        self.elements.set_element(self.grid.clone());
         self.cells.clear();
         Ok(())
     }
@@ -264,6 +481,13 @@ impl DomBackend {
         }
         Ok(())
     }
+
+    /// Returns the index of the cell at the given position, if it is inside
+    /// the grid.
+    fn cell_index(&self, x: u16, y: u16) -> Option<usize> {
+        let index = y as usize * self.size.width as usize + x as usize;
+        (x < self.size.width && index < self.cells.len()).then_some(index)
+    }
 }
 
 impl CellSized for DomBackend {
@@ -308,16 +532,24 @@ impl Backend for DomBackend {
                 self.cell_size = Self::measure_cell_size(&self.document, &self.grid_parent)
                     .unwrap_or(DEFAULT_CELL_SIZE);
                 self.size = Self::calculate_size(&self.grid_parent, self.cell_size);
+                self.update_mouse_config();
             }
 
             self.grid_parent
                 .append_child(&self.grid)
                 .map_err(Error::from)?;
             self.populate()?;
+            self.update_ime_input()?;
+
+            if std::mem::take(&mut self.options.autofocus) {
+                focus_element(&self.grid)?;
+            }
         }
 
         for (x, y, cell) in content {
-            let cell_position = (y * self.size.width + x) as usize;
+            let cell_position = self
+                .cell_index(x, y)
+                .ok_or_else(|| IoError::other(format!("cell ({x}, {y}) is outside of the grid")))?;
             let elem = &self.cells[cell_position];
 
             elem.set_inner_html(cell.symbol());
@@ -341,16 +573,26 @@ impl Backend for DomBackend {
 
     /// This function is called after the [`DomBackend::draw`] function.
     ///
-    /// This function does nothing because the content is directly
-    /// displayed by the draw function.
+    /// The content is directly displayed by the draw function, so this only
+    /// updates the mouse cursor and notifies the resize handler.
     fn flush(&mut self) -> IoResult<()> {
+        self.update_mouse_cursor()?;
+
+        if self.resize_callback.is_some() {
+            let size = self.window_size()?.into();
+            if let Some(resize_callback) = &mut self.resize_callback {
+                resize_callback.update(size);
+            }
+        }
+
         Ok(())
     }
 
     fn hide_cursor(&mut self) -> IoResult<()> {
-        if let Some(pos) = self.cursor_position {
-            let cell_position = (pos.y * self.size.width + pos.x) as usize;
-
+        if let Some(cell_position) = self
+            .cursor_position
+            .and_then(|pos| self.cell_index(pos.x, pos.y))
+        {
             // Use CursorShape::None to clear cursor CSS
             update_css_field(
                 CursorShape::None.get_css_attribute(),
@@ -364,8 +606,10 @@ impl Backend for DomBackend {
 
     fn show_cursor(&mut self) -> IoResult<()> {
         // Remove cursor at last position
-        if let Some(pos) = self.last_cursor_position {
-            let cell_position = (pos.y * self.size.width + pos.x) as usize;
+        if let Some(cell_position) = self
+            .last_cursor_position
+            .and_then(|pos| self.cell_index(pos.x, pos.y))
+        {
             update_css_field(
                 CursorShape::None.get_css_attribute(),
                 &self.cells[cell_position],
@@ -374,9 +618,10 @@ impl Backend for DomBackend {
         }
 
         // Show cursor at current position
-        if let Some(pos) = self.cursor_position {
-            let cell_position = (pos.y * self.size.width + pos.x) as usize;
-
+        if let Some(cell_position) = self
+            .cursor_position
+            .and_then(|pos| self.cell_index(pos.x, pos.y))
+        {
             update_css_field(
                 self.options.cursor_shape.get_css_attribute(),
                 &self.cells[cell_position],
@@ -429,6 +674,11 @@ impl Backend for DomBackend {
         self.last_cursor_position = self.cursor_position;
         self.cursor_position = Some(position.into());
 
+        if let (Some(ime_input), Some(position)) = (&self.ime_input, self.cursor_position) {
+            let (width, height) = self.cell_size_css_px();
+            ime_input.set_cursor_position(position, (width.into(), height.into()));
+        }
+
         Ok(())
     }
 
@@ -441,28 +691,19 @@ impl Backend for DomBackend {
 }
 
 impl WebEventHandler for DomBackend {
-    fn on_mouse_event<F>(&mut self, mut callback: F) -> Result<(), Error>
+    fn on_mouse_event<F>(&mut self, callback: F) -> Result<(), Error>
     where
         F: FnMut(MouseEvent) + 'static,
     {
         // Clear any existing handlers first
         self.clear_mouse_events();
 
-        // Configure coordinate translation for DOM backend
-        // Cell dimensions are derived from element dimensions / grid size
-        let config = MouseConfig::new(self.size.width, self.size.height);
-
-        // Use the grid element for coordinate calculation
-        let element = self.grid.clone();
-
-        // Create mouse event callback
-        let mouse_callback = EventCallback::new(
+        // Create mouse event callback, using the grid element for coordinate calculation
+        let mouse_callback = create_mouse_callback(
             self.grid.clone(),
             MOUSE_EVENT_TYPES,
-            move |event: web_sys::MouseEvent| {
-                let mouse_event = create_mouse_event(&event, &element, &config);
-                callback(mouse_event);
-            },
+            self.shared_mouse_config.clone(),
+            callback,
         )?;
 
         self.mouse_callback = Some(mouse_callback);
@@ -474,7 +715,28 @@ impl WebEventHandler for DomBackend {
         self.mouse_callback = None;
     }
 
-    fn on_key_event<F>(&mut self, mut callback: F) -> Result<(), Error>
+    fn on_gesture_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(GestureEvent) + 'static,
+    {
+        // Clear any existing handlers first
+        self.clear_gesture_events();
+
+        // Listen on the grid parent, as the grid is replaced on resize
+        self.gesture_callback = Some(create_gesture_callback(
+            self.grid_parent.clone(),
+            self.shared_mouse_config.clone(),
+            callback,
+        )?);
+
+        Ok(())
+    }
+
+    fn clear_gesture_events(&mut self) {
+        self.gesture_callback = None;
+    }
+
+    fn on_key_event<F>(&mut self, callback: F) -> Result<(), Error>
     where
         F: FnMut(KeyEvent) + 'static,
     {
@@ -484,12 +746,14 @@ impl WebEventHandler for DomBackend {
         // Make the grid element focusable so it can receive key events
         self.grid.set_attribute("tabindex", "0")?;
 
-        self.key_callback = Some(EventCallback::new(
-            self.grid.clone(),
-            KEY_EVENT_TYPES,
-            move |event: web_sys::KeyboardEvent| {
-                callback(event.into());
-            },
+        // Listen on the grid parent, as the grid is replaced on resize
+        self.key_callback = Some(create_key_callback(
+            self.grid_parent.clone(),
+            self.elements.clone(),
+            self.options.report_key_release_events,
+            self.options.key_capture.clone(),
+            self.options.document_key_events,
+            callback,
         )?);
 
         Ok(())
@@ -498,4 +762,305 @@ impl WebEventHandler for DomBackend {
     fn clear_key_events(&mut self) {
         self.key_callback = None;
     }
+
+    fn on_paste_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(String) + 'static,
+    {
+        // Clear any existing handlers first
+        self.clear_paste_events();
+
+        // Make the grid element focusable so it can receive paste events
+        self.grid.set_attribute("tabindex", "0")?;
+
+        // Listen on the grid parent, as the grid is replaced on resize
+        self.paste_callback = Some(create_paste_callback(
+            self.grid_parent.clone(),
+            self.elements.clone(),
+            callback,
+        )?);
+
+        Ok(())
+    }
+
+    fn clear_paste_events(&mut self) {
+        self.paste_callback = None;
+    }
+
+    fn on_focus_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(FocusEvent) + 'static,
+    {
+        // Clear any existing handlers first
+        self.clear_focus_events();
+
+        self.focus_callback = Some(create_focus_callback(
+            self.grid_parent.clone(),
+            self.elements.clone(),
+            callback,
+        )?);
+
+        Ok(())
+    }
+
+    fn clear_focus_events(&mut self) {
+        self.focus_callback = None;
+    }
+
+    fn on_resize<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(ResizeEvent) + 'static,
+    {
+        let size = self.window_size().ok().map(Into::into);
+        self.resize_callback = Some(ResizeCallback::new(size, callback));
+        Ok(())
+    }
+
+    fn clear_resize_events(&mut self) {
+        self.resize_callback = None;
+    }
+
+    fn focus(&self) -> Result<(), Error> {
+        focus_element(&self.grid)
+    }
+
+    fn blur(&self) -> Result<(), Error> {
+        blur_element(&self.elements)
+    }
+
+    fn has_focus(&self) -> bool {
+        element_has_focus(&self.elements)
+    }
+
+    fn on_ime_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(ImeEvent) + 'static,
+    {
+        // Clear any existing handlers first
+        self.clear_ime_events();
+
+        // Make the grid element focusable so it can hand focus to the IME input
+        self.grid.set_attribute("tabindex", "0")?;
+
+        let (width, height) = self.cell_size_css_px();
+        self.ime_input = Some(ImeInput::new(
+            &self.elements,
+            &self.grid_parent,
+            (width.into(), height.into()),
+            0.0,
+            callback,
+        )?);
+
+        Ok(())
+    }
+
+    fn clear_ime_events(&mut self) {
+        self.ime_input = None;
+    }
+
+    fn on_gamepad_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(GamepadEvent) + 'static,
+    {
+        self.gamepad_callback = Some(GamepadCallback::new(
+            self.options
+                .gamepad_dead_zone
+                .unwrap_or(DEFAULT_GAMEPAD_DEAD_ZONE),
+            callback,
+        ));
+        Ok(())
+    }
+
+    fn clear_gamepad_events(&mut self) {
+        self.gamepad_callback = None;
+    }
+
+    fn poll_gamepads(&mut self) -> Option<bool> {
+        self.gamepad_callback.as_mut().map(GamepadCallback::poll)
+    }
+
+    fn ime_input_element(&self) -> Option<Element> {
+        self.ime_input.as_ref().map(ImeInput::textarea)
+    }
+
+    fn handles_document_key_events(&self) -> bool {
+        self.options.document_key_events
+    }
+
+    fn handles_gesture_events(&self) -> bool {
+        self.options.gesture_events
+    }
+
+    /// Returns the parent of the grid, as the grid itself is replaced when
+    /// the terminal is resized.
+    fn terminal_element(&self) -> Option<Element> {
+        Some(self.grid_parent.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::Event;
+    use wasm_bindgen_test::*;
+
+    use crate::backend::event_callback::mouse_event_matrix;
+
+    #[wasm_bindgen_test]
+    fn test_mouse_event_matrix() {
+        let mut backend = DomBackend::new().expect("failed to create backend");
+        let target: Element = backend.grid.clone();
+        mouse_event_matrix::check(&mut backend, &target);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_focus_with_ime_input() {
+        let mut backend = DomBackend::new().expect("failed to create backend");
+        let events = Rc::new(RefCell::new(Vec::new()));
+        // Both handlers are set before the grid is placed in the document
+        backend
+            .on_focus_event({
+                let events = events.clone();
+                move |event| events.borrow_mut().push(event)
+            })
+            .expect("failed to set focus handler");
+        backend
+            .on_ime_event(|_| {})
+            .expect("failed to set IME handler");
+
+        for _ in 0..2 {
+            backend.draw(std::iter::empty()).expect("failed to draw");
+            backend.focus().expect("failed to focus");
+            let active = backend
+                .document
+                .active_element()
+                .expect("no focused element");
+            assert!(backend.elements.is_ime_input(&active));
+            assert!(backend.grid_parent.contains(Some(&active)));
+            assert!(backend.has_focus());
+
+            backend.blur().expect("failed to blur");
+            assert!(!backend.has_focus());
+
+            // Replaces the grid on the next draw, as on resize
+            backend.initialized.replace(false);
+        }
+
+        assert_eq!(
+            *events.borrow(),
+            [
+                FocusEvent::Gained,
+                FocusEvent::Lost,
+                FocusEvent::Gained,
+                FocusEvent::Lost
+            ]
+        );
+    }
+
+    fn paste(element: &Element, text: &str) {
+        let data = web_sys::DataTransfer::new().expect("failed to create data transfer");
+        data.set_data("text/plain", text)
+            .expect("failed to set clipboard data");
+        let init = web_sys::ClipboardEventInit::new();
+        init.set_clipboard_data(Some(&data));
+        init.set_bubbles(true);
+        init.set_cancelable(true);
+        let event = web_sys::ClipboardEvent::new_with_event_init_dict("paste", &init)
+            .expect("failed to create paste event");
+        element
+            .dispatch_event(&event)
+            .expect("failed to dispatch paste event");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_paste_after_resize() {
+        let mut backend = DomBackend::new().expect("failed to create backend");
+        let pasted = Rc::new(RefCell::new(Vec::new()));
+        backend
+            .on_paste_event({
+                let pasted = pasted.clone();
+                move |text| pasted.borrow_mut().push(text)
+            })
+            .expect("failed to set paste handler");
+
+        for text in ["first", "second"] {
+            backend.draw(std::iter::empty()).expect("failed to draw");
+            paste(&backend.grid, text);
+
+            // Replaces the grid on the next draw, as on resize
+            backend.initialized.replace(false);
+        }
+
+        assert_eq!(*pasted.borrow(), ["first", "second"]);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_event_during_callback() {
+        let mut backend = DomBackend::new().expect("failed to create backend");
+        backend.draw(std::iter::empty()).expect("failed to draw");
+        let events = Rc::new(RefCell::new(Vec::new()));
+        backend
+            .on_event({
+                let events = events.clone();
+                let grid = backend.grid.clone();
+                move |event| {
+                    if event == Event::Paste("first".into()) {
+                        paste(&grid, "second");
+                    }
+                    events.borrow_mut().push(event);
+                }
+            })
+            .expect("failed to set event handler");
+
+        paste(&backend.grid, "first");
+
+        assert_eq!(
+            *events.borrow(),
+            [Event::Paste("first".into()), Event::Paste("second".into())]
+        );
+    }
+
+    #[wasm_bindgen_test]
+    fn test_key_events_with_ime_input() {
+        for document_key_events in [false, true] {
+            let mut backend = DomBackend::new_with_options(
+                DomBackendOptions::default().document_key_events(document_key_events),
+            )
+            .expect("failed to create backend");
+            let events = Rc::new(RefCell::new(Vec::new()));
+            backend
+                .on_key_event({
+                    let events = events.clone();
+                    move |event| events.borrow_mut().push(event)
+                })
+                .expect("failed to set key handler");
+            backend
+                .on_ime_event(|_| {})
+                .expect("failed to set IME handler");
+            backend.draw(std::iter::empty()).expect("failed to draw");
+
+            // Replaces the grid and the IME input, as on resize
+            backend.initialized.replace(false);
+            backend.draw(std::iter::empty()).expect("failed to draw");
+
+            let ime_input = backend
+                .grid_parent
+                .query_selector("[data-ratzilla-ime]")
+                .expect("invalid selector")
+                .expect("no IME input");
+            let init = web_sys::KeyboardEventInit::new();
+            init.set_key("a");
+            init.set_bubbles(true);
+            init.set_cancelable(true);
+            let event = web_sys::KeyboardEvent::new_with_keyboard_event_init_dict("keydown", &init)
+                .expect("failed to create key event");
+            ime_input
+                .dispatch_event(&event)
+                .expect("failed to dispatch key event");
+
+            // The event bubbles up to the listener, and so does the copy
+            // forwarded to the grid, but only one of them is reported
+            assert_eq!(events.borrow().len(), 1);
+        }
+    }
 }
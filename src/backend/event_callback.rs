@@ -3,22 +3,37 @@
 //! This module provides utilities for managing web event listeners with proper
 //! lifecycle management and coordinate translation for mouse events.
 
-use std::fmt::Formatter;
+use std::{
+    cell::RefCell,
+    fmt::Formatter,
+    rc::{Rc, Weak},
+};
 use web_sys::{
+    js_sys::Array,
     wasm_bindgen::{convert::FromWasmAbi, prelude::Closure, JsCast},
-    Element, EventTarget,
+    Element, EventTarget, IntersectionObserver, IntersectionObserverEntry,
 };
 
 use crate::{
+    backend::{
+        ime::TerminalElements,
+        key_capture::KeyCapture,
+        touch::{
+            disable_touch_actions, TouchInput, TouchMouseTracker, TouchPhase, LONG_PRESS_DURATION,
+        },
+        utils::{get_device_pixel_ratio, get_document},
+    },
     error::Error,
-    event::{MouseButton, MouseEvent, MouseEventKind},
+    event::{
+        CellOffset, FocusEvent, KeyEvent, MouseButton, MouseEvent, MouseEventKind, ResizeEvent,
+    },
 };
 
 /// Manages web event listeners with automatic cleanup.
 ///
 /// When this struct is dropped, all registered event listeners are removed
 /// from the element, preventing memory leaks.
-pub(super) struct EventCallback<T: 'static> {
+pub(crate) struct EventCallback<T: 'static> {
     /// The event types this callback is registered for.
     event_types: &'static [&'static str],
     /// The event target the listeners are attached to.
@@ -75,11 +90,59 @@ impl<T: 'static> std::fmt::Debug for EventCallback<T> {
     }
 }
 
+/// Observes whether an element intersects the viewport, with automatic
+/// cleanup.
+///
+/// When this struct is dropped, the observer is disconnected.
+pub(crate) struct IntersectionCallback {
+    /// The observer watching the element.
+    observer: IntersectionObserver,
+    /// The closure that handles the intersection changes.
+    #[allow(dead_code)]
+    closure: Closure<dyn FnMut(Array)>,
+}
+
+impl IntersectionCallback {
+    /// Creates a new [`IntersectionCallback`] that calls the callback with
+    /// whether the element is in view, initially and on every change.
+    pub fn new<F>(element: &Element, mut callback: F) -> Result<Self, Error>
+    where
+        F: FnMut(bool) + 'static,
+    {
+        let closure = Closure::<dyn FnMut(Array)>::new(move |entries: Array| {
+            let entry = entries
+                .iter()
+                .filter_map(|entry| entry.dyn_into::<IntersectionObserverEntry>().ok())
+                .next_back();
+            if let Some(entry) = entry {
+                callback(entry.is_intersecting());
+            }
+        });
+        let observer = IntersectionObserver::new(closure.as_ref().unchecked_ref())?;
+        observer.observe(element);
+        Ok(Self { observer, closure })
+    }
+}
+
+impl Drop for IntersectionCallback {
+    fn drop(&mut self) {
+        self.observer.disconnect();
+    }
+}
+
+impl std::fmt::Debug for IntersectionCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("IntersectionCallback")
+            .field("observer", &self.observer)
+            .finish()
+    }
+}
+
 /// Configuration for mouse coordinate transformation.
 ///
 /// This struct holds the information needed to translate raw pixel coordinates
 /// from mouse events into terminal grid coordinates.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub(super) struct MouseConfig {
     /// Terminal grid width in characters.
     pub grid_width: u16,
@@ -90,6 +153,12 @@ pub(super) struct MouseConfig {
     /// Cell dimensions in pixels (width, height).
     /// If provided, used for pixel-perfect coordinate calculation.
     pub cell_dimensions: Option<(f64, f64)>,
+    /// Whether to call `preventDefault` on `wheel` events so that the page
+    /// does not scroll while the pointer is over the terminal.
+    pub prevent_default_scroll: bool,
+    /// Whether to stop the browser from panning and zooming the page on
+    /// touches, so that dragging a finger is reported as a mouse drag.
+    pub prevent_default_touch: bool,
 }
 
 impl MouseConfig {
@@ -100,6 +169,8 @@ impl MouseConfig {
             grid_height,
             offset: None,
             cell_dimensions: None,
+            prevent_default_scroll: false,
+            prevent_default_touch: false,
         }
     }
 
@@ -114,12 +185,86 @@ impl MouseConfig {
         self.cell_dimensions = Some((width, height));
         self
     }
+
+    /// Sets whether `wheel` events should have their default action prevented.
+    pub fn with_prevent_default_scroll(mut self, prevent: bool) -> Self {
+        self.prevent_default_scroll = prevent;
+        self
+    }
+
+    /// Sets whether touches should be prevented from panning and zooming the
+    /// page.
+    pub fn with_prevent_default_touch(mut self, prevent: bool) -> Self {
+        self.prevent_default_touch = prevent;
+        self
+    }
 }
 
+/// A [`MouseConfig`] shared between a backend and its event callbacks, so
+/// that the backend can update it when the terminal is resized.
+pub(super) type SharedMouseConfig = Rc<RefCell<MouseConfig>>;
+
 /// The event types for keyboard events.
 pub(super) const KEY_EVENT_TYPES: &[&str] = &["keydown"];
 
-/// Mouse event types (excluding wheel which needs special handling).
+/// The event types for keyboard events, including key releases.
+pub(super) const KEY_EVENT_TYPES_WITH_RELEASE: &[&str] = &["keydown", "keyup"];
+
+/// Returns the keyboard event types to listen for.
+fn key_event_types(report_release: bool) -> &'static [&'static str] {
+    if report_release {
+        KEY_EVENT_TYPES_WITH_RELEASE
+    } else {
+        KEY_EVENT_TYPES
+    }
+}
+
+/// Creates a keyboard event callback for the terminal element.
+///
+/// The listeners are attached to `element`, which is either the terminal
+/// element or an element containing it, e.g. so that the listeners survive
+/// the terminal element being replaced. Keys captured by `capture` have their
+/// default action prevented. If `on_document` is set, the listeners are
+/// attached to the document instead, so that keys are received regardless of
+/// which element has focus. Events of the IME input are skipped, as they are
+/// forwarded to the terminal element and bubble up to the listeners.
+pub(super) fn create_key_callback<F>(
+    element: Element,
+    elements: TerminalElements,
+    report_release: bool,
+    capture: KeyCapture,
+    on_document: bool,
+    mut callback: F,
+) -> Result<EventCallback<web_sys::KeyboardEvent>, Error>
+where
+    F: FnMut(KeyEvent) + 'static,
+{
+    let target: EventTarget = if on_document {
+        get_document()?.into()
+    } else {
+        element.into()
+    };
+    EventCallback::new(
+        target,
+        key_event_types(report_release),
+        move |event: web_sys::KeyboardEvent| {
+            let from_ime_input = event
+                .target()
+                .and_then(|target| target.dyn_into::<Element>().ok())
+                .is_some_and(|target| elements.is_ime_input(&target));
+            if from_ime_input {
+                return;
+            }
+            let key_event = KeyEvent::from(event.clone());
+            if capture.captures(&key_event) {
+                event.prevent_default();
+            }
+            callback(key_event);
+        },
+    )
+}
+
+/// Mouse event types.
 pub(super) const MOUSE_EVENT_TYPES: &[&str] = &[
     "mousemove",
     "mousedown",
@@ -128,17 +273,40 @@ pub(super) const MOUSE_EVENT_TYPES: &[&str] = &[
     "dblclick",
     "mouseenter",
     "mouseleave",
+    "wheel",
+    "pointerdown",
+    "pointermove",
+    "pointerup",
+    "pointercancel",
+    "contextmenu",
 ];
 
+/// Paste event types.
+pub(super) const PASTE_EVENT_TYPES: &[&str] = &["paste"];
+
+/// Focus event types.
+pub(super) const FOCUS_EVENT_TYPES: &[&str] = &["focusin", "focusout"];
+
 /// Translates mouse event pixel coordinates to terminal grid coordinates.
 ///
 /// This function calculates the grid position (col, row) from raw pixel
 /// coordinates, taking into account element positioning and optional offsets.
-fn mouse_to_grid_coords(
+pub(super) fn mouse_to_grid_coords(
     event: &web_sys::MouseEvent,
     element: &Element,
     config: &MouseConfig,
 ) -> (u16, u16) {
+    let (col, row, _) = mouse_to_grid_position(event, element, config);
+    (col, row)
+}
+
+/// Translates mouse event pixel coordinates to terminal grid coordinates,
+/// along with the position of the pointer within the cell.
+fn mouse_to_grid_position(
+    event: &web_sys::MouseEvent,
+    element: &Element,
+    config: &MouseConfig,
+) -> (u16, u16, Option<CellOffset>) {
     let rect = element.get_bounding_client_rect();
 
     // Calculate relative position within element
@@ -157,20 +325,48 @@ fn mouse_to_grid_coords(
 
     // Avoid division by zero
     if drawable_width <= 0.0 || drawable_height <= 0.0 {
-        return (0, 0);
+        return (0, 0, None);
     }
 
-    // Map to grid coordinates
-    let col = ((relative_x / drawable_width) * config.grid_width as f64) as u16;
-    let row = ((relative_y / drawable_height) * config.grid_height as f64) as u16;
-
-    // Clamp to bounds
-    (
-        col.min(config.grid_width.saturating_sub(1)),
-        row.min(config.grid_height.saturating_sub(1)),
+    locate_in_grid(
+        (relative_x, relative_y),
+        (drawable_width, drawable_height),
+        (config.grid_width, config.grid_height),
+        get_device_pixel_ratio() as f64,
     )
 }
 
+/// Maps a position within the drawable area of the grid to the cell that
+/// contains it, and the offset within that cell.
+///
+/// Positions outside of the grid are clamped to its edge.
+fn locate_in_grid(
+    (x, y): (f64, f64),
+    (drawable_width, drawable_height): (f64, f64),
+    (grid_width, grid_height): (u16, u16),
+    device_pixel_ratio: f64,
+) -> (u16, u16, Option<CellOffset>) {
+    if grid_width == 0 || grid_height == 0 {
+        return (0, 0, None);
+    }
+    let cell_width = drawable_width / grid_width as f64;
+    let cell_height = drawable_height / grid_height as f64;
+
+    // Map to grid coordinates, clamped to bounds
+    let col = ((x / cell_width) as u16).min(grid_width - 1);
+    let row = ((y / cell_height) as u16).min(grid_height - 1);
+
+    let offset = CellOffset::new(
+        (
+            (x - col as f64 * cell_width).clamp(0.0, cell_width),
+            (y - row as f64 * cell_height).clamp(0.0, cell_height),
+        ),
+        (cell_width, cell_height),
+        device_pixel_ratio,
+    );
+    (col, row, Some(offset))
+}
+
 /// Converts a web_sys::MouseEvent type string to a MouseEventKind.
 fn event_type_to_kind(event_type: &str, button: MouseButton) -> MouseEventKind {
     match event_type {
@@ -185,23 +381,483 @@ fn event_type_to_kind(event_type: &str, button: MouseButton) -> MouseEventKind {
     }
 }
 
+/// Returns the button reported for a drag, given the `buttons` bitmask of a
+/// mouse event.
+///
+/// If several buttons are held, the primary one wins.
+fn held_button(buttons: u16) -> Option<MouseButton> {
+    [
+        (1, MouseButton::Left),
+        (2, MouseButton::Right),
+        (4, MouseButton::Middle),
+        (8, MouseButton::Back),
+        (16, MouseButton::Forward),
+    ]
+    .into_iter()
+    .find(|(mask, _)| buttons & mask != 0)
+    .map(|(_, button)| button)
+}
+
+/// Converts the deltas of a `wheel` event to a scroll [`MouseEventKind`].
+///
+/// The dominant axis wins, so diagonal trackpad gestures produce a single
+/// event instead of one per axis. Returns `None` if there is no scroll along
+/// either axis, e.g. for wheel events along the z axis only.
+fn wheel_delta_to_kind(delta_x: f64, delta_y: f64) -> Option<MouseEventKind> {
+    if delta_y.abs() >= delta_x.abs() {
+        if delta_y < 0.0 {
+            Some(MouseEventKind::ScrollUp)
+        } else if delta_y > 0.0 {
+            Some(MouseEventKind::ScrollDown)
+        } else {
+            None
+        }
+    } else if delta_x < 0.0 {
+        Some(MouseEventKind::ScrollLeft)
+    } else {
+        Some(MouseEventKind::ScrollRight)
+    }
+}
+
 /// Creates a MouseEvent from web_sys events with coordinate translation.
+///
+/// Returns `None` for wheel events that don't scroll along either axis.
 pub(super) fn create_mouse_event(
     event: &web_sys::MouseEvent,
     element: &Element,
     config: &MouseConfig,
-) -> MouseEvent {
-    let (col, row) = mouse_to_grid_coords(event, element, config);
+) -> Option<MouseEvent> {
+    let (col, row, offset) = mouse_to_grid_position(event, element, config);
     let button: MouseButton = event.button().into();
     let event_type = event.type_();
 
+    let kind = match event.dyn_ref::<web_sys::WheelEvent>() {
+        Some(wheel) => wheel_delta_to_kind(wheel.delta_x(), wheel.delta_y())?,
+        None if event_type == "mousemove" => match held_button(event.buttons()) {
+            Some(button) => MouseEventKind::Drag(button),
+            None => MouseEventKind::Moved,
+        },
+        None => event_type_to_kind(&event_type, button),
+    };
+
+    Some(MouseEvent {
+        kind,
+        col,
+        row,
+        offset,
+        ctrl: event.ctrl_key(),
+        alt: event.alt_key(),
+        shift: event.shift_key(),
+        meta: event.meta_key(),
+    })
+}
+
+/// Creates an [`EventCallback`] that translates mouse events on the element
+/// to [`MouseEvent`]s with grid coordinates.
+///
+/// Touch input, received as pointer events, is mapped to mouse events by a
+/// [`TouchMouseTracker`], and the mouse events the browser emulates for
+/// touches are ignored.
+///
+/// The pointer is captured while a mouse button is held, so that drags and
+/// the final button release are reported even outside of the element.
+///
+/// `wheel` events have their default action prevented if
+/// [`MouseConfig::prevent_default_scroll`] is set. The rest of the config is
+/// read for every event, so that updates after a resize are picked up.
+pub(super) fn create_mouse_callback<F>(
+    element: Element,
+    event_types: &'static [&'static str],
+    config: SharedMouseConfig,
+    callback: F,
+) -> Result<EventCallback<web_sys::MouseEvent>, Error>
+where
+    F: FnMut(MouseEvent) + 'static,
+{
+    if config.borrow().prevent_default_touch {
+        disable_touch_actions(&element);
+    }
+    // Shared with the long press timers
+    let touch = Rc::new(RefCell::new(TouchMouseTracker::default()));
+    let callback = Rc::new(RefCell::new(callback));
+    let target = element.clone();
+    EventCallback::new(target, event_types, move |event: web_sys::MouseEvent| {
+        let config = config.borrow().clone();
+        if let Some(pointer) = event.dyn_ref::<web_sys::PointerEvent>() {
+            if let Some(phase) = TouchPhase::from_event_type(&pointer.type_()) {
+                let Some(input) = TouchInput::from_pointer_event(pointer, &element, &config) else {
+                    // Mouse and pen input is reported via mouse events, which
+                    // follow the pointer capture
+                    if phase == TouchPhase::Down {
+                        let _ = element.set_pointer_capture(pointer.pointer_id());
+                    }
+                    return;
+                };
+                if input.phase == TouchPhase::Down {
+                    // Suppresses the emulated mouse events, which also means
+                    // the element has to be focused here
+                    event.prevent_default();
+                    if element.has_attribute("tabindex") {
+                        let _ = focus_element(&element);
+                    }
+                    schedule_long_press(
+                        input,
+                        event.clone(),
+                        Rc::downgrade(&touch),
+                        Rc::downgrade(&callback),
+                    );
+                }
+                let events = touch.borrow_mut().handle(input);
+                let mut callback = callback.borrow_mut();
+                for (kind, col, row) in events {
+                    callback(touch_mouse_event(kind, col, row, &event));
+                }
+                return;
+            }
+        }
+        let event_type = event.type_();
+        let is_wheel = event_type == "wheel";
+        if !is_wheel && touch.borrow().is_emulated_mouse_event(event.time_stamp()) {
+            if event_type == "contextmenu" {
+                // Long presses are reported as right clicks instead
+                event.prevent_default();
+            }
+            return;
+        } else if event_type == "contextmenu" {
+            return;
+        }
+        let Some(mouse_event) = create_mouse_event(&event, &element, &config) else {
+            // Wheel events without a scroll keep their default action
+            return;
+        };
+        if is_wheel && config.prevent_default_scroll {
+            event.prevent_default();
+        }
+        let triple_click = match mouse_event.kind {
+            MouseEventKind::SingleClick(button) if event.detail() == 3 => Some(button),
+            _ => None,
+        };
+        let mut callback = callback.borrow_mut();
+        callback(mouse_event.clone());
+        if let Some(button) = triple_click {
+            callback(MouseEvent {
+                kind: MouseEventKind::TripleClick(button),
+                ..mouse_event
+            });
+        }
+    })
+}
+
+/// Returns the [`MouseEvent`] a touch maps to, with the modifiers of the
+/// pointer event.
+fn touch_mouse_event(
+    kind: MouseEventKind,
+    col: u16,
+    row: u16,
+    event: &web_sys::MouseEvent,
+) -> MouseEvent {
     MouseEvent {
-        kind: event_type_to_kind(&event_type, button),
+        kind,
         col,
         row,
+        offset: None,
         ctrl: event.ctrl_key(),
         alt: event.alt_key(),
         shift: event.shift_key(),
+        meta: event.meta_key(),
+    }
+}
+
+/// Reports the touch that started with `input` as a right click once the
+/// finger has been held down for [`LONG_PRESS_DURATION`], unless it was
+/// lifted or started a drag before.
+///
+/// Nothing is reported if the mouse handler was removed in the meantime.
+fn schedule_long_press<F>(
+    input: TouchInput,
+    event: web_sys::MouseEvent,
+    touch: Weak<RefCell<TouchMouseTracker>>,
+    callback: Weak<RefCell<F>>,
+) where
+    F: FnMut(MouseEvent) + 'static,
+{
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let timeout = Closure::once_into_js(move || {
+        let (Some(touch), Some(callback)) = (touch.upgrade(), callback.upgrade()) else {
+            return;
+        };
+        let events = touch.borrow_mut().long_press(input.pointer_id, input.time);
+        let Ok(mut callback) = callback.try_borrow_mut() else {
+            return;
+        };
+        for (kind, col, row) in events {
+            callback(touch_mouse_event(kind, col, row, &event));
+        }
+    });
+    let _ = window.set_timeout_with_callback_and_timeout_and_arguments_0(
+        timeout.unchecked_ref(),
+        LONG_PRESS_DURATION as i32,
+    );
+}
+
+/// Creates an [`EventCallback`] that reports the plain text of `paste`
+/// events on the element.
+///
+/// The default action is prevented, and line endings are normalized to `\n`.
+/// Pastes without any text (e.g. images) are ignored, and keep their default
+/// action. The element may contain
+/// the terminal element, e.g. so that the listener survives the terminal
+/// element being replaced. Events of the IME input are skipped then, as they
+/// are forwarded to the terminal element.
+pub(super) fn create_paste_callback<F>(
+    element: Element,
+    elements: TerminalElements,
+    mut callback: F,
+) -> Result<EventCallback<web_sys::ClipboardEvent>, Error>
+where
+    F: FnMut(String) + 'static,
+{
+    EventCallback::new(
+        element,
+        PASTE_EVENT_TYPES,
+        move |event: web_sys::ClipboardEvent| {
+            let from_ime_input = event
+                .target()
+                .and_then(|target| target.dyn_into::<Element>().ok())
+                .is_some_and(|target| elements.is_ime_input(&target));
+            if from_ime_input {
+                return;
+            }
+            let Some(text) = event
+                .clipboard_data()
+                .and_then(|data| data.get_data("text/plain").ok())
+                .filter(|text| !text.is_empty())
+            else {
+                return;
+            };
+            event.prevent_default();
+            callback(normalize_line_endings(&text));
+        },
+    )
+}
+
+/// Converts `\r\n` and lone `\r` line endings to `\n`.
+fn normalize_line_endings(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Returns whether the terminal element, or its IME input, has focus.
+pub(super) fn element_has_focus(elements: &TerminalElements) -> bool {
+    get_document()
+        .ok()
+        .and_then(|document| document.active_element())
+        .is_some_and(|active| elements.contains(&active))
+}
+
+/// Focuses the terminal element, making it focusable first if needed.
+///
+/// If the element has an IME input, focus is handed over to it.
+pub(super) fn focus_element(element: &Element) -> Result<(), Error> {
+    if !element.has_attribute("tabindex") {
+        element.set_attribute("tabindex", "0")?;
+    }
+    let element: &web_sys::HtmlElement = element
+        .dyn_ref()
+        .ok_or(Error::UnableToRetrieveComponent("HtmlElement"))?;
+    Ok(element.focus()?)
+}
+
+/// Removes focus from the terminal element, or its IME input.
+pub(super) fn blur_element(elements: &TerminalElements) -> Result<(), Error> {
+    let active = get_document()?.active_element();
+    match active {
+        Some(active) if elements.contains(&active) => {
+            if let Some(active) = active.dyn_ref::<web_sys::HtmlElement>() {
+                active.blur()?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Creates an [`EventCallback`] that reports when the terminal element gains
+/// or loses focus.
+///
+/// The listeners are attached to `container`, the element the terminal
+/// element and its IME input are placed in, so that focus moving between the
+/// two is not reported.
+pub(super) fn create_focus_callback<F>(
+    container: Element,
+    elements: TerminalElements,
+    mut callback: F,
+) -> Result<EventCallback<web_sys::FocusEvent>, Error>
+where
+    F: FnMut(FocusEvent) + 'static,
+{
+    let mut focused = element_has_focus(&elements);
+    EventCallback::new(
+        container,
+        FOCUS_EVENT_TYPES,
+        move |event: web_sys::FocusEvent| {
+            let is_terminal = |target: Option<EventTarget>| {
+                target
+                    .and_then(|target| target.dyn_into::<Element>().ok())
+                    .is_some_and(|target| elements.contains(&target))
+            };
+            let has_focus = if event.type_() == "focusin" {
+                is_terminal(event.target())
+            } else if is_terminal(event.target()) {
+                is_terminal(event.related_target())
+            } else {
+                return;
+            };
+            if has_focus != focused {
+                focused = has_focus;
+                callback(if has_focus {
+                    FocusEvent::Gained
+                } else {
+                    FocusEvent::Lost
+                });
+            }
+        },
+    )
+}
+
+/// Reports changes in the size of the terminal to a callback.
+///
+/// Backends pass their current size to [`ResizeCallback::update`] on every
+/// flush, and the callback is only called when the size actually changed.
+pub(super) struct ResizeCallback {
+    /// The last size that was reported, if known.
+    size: Option<ResizeEvent>,
+    /// The callback to call on resize.
+    callback: Box<dyn FnMut(ResizeEvent)>,
+}
+
+impl ResizeCallback {
+    /// Creates a new [`ResizeCallback`], starting at the given size.
+    ///
+    /// If the current size isn't known, the first update only records it.
+    pub fn new<F>(size: Option<ResizeEvent>, callback: F) -> Self
+    where
+        F: FnMut(ResizeEvent) + 'static,
+    {
+        Self {
+            size,
+            callback: Box::new(callback),
+        }
+    }
+
+    /// Calls the callback if `size` differs from the last reported size.
+    pub fn update(&mut self, size: ResizeEvent) {
+        let previous = self.size.replace(size);
+        if previous.is_some_and(|previous| previous != size) {
+            (self.callback)(size);
+        }
+    }
+}
+
+impl std::fmt::Debug for ResizeCallback {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResizeCallback")
+            .field("size", &self.size)
+            .finish()
+    }
+}
+
+/// The mouse events every backend has to report, checked by the tests of
+/// each backend so that they can't drift apart.
+#[cfg(test)]
+pub(super) mod mouse_event_matrix {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use crate::render::WebEventHandler;
+
+    /// Creates a synthetic mouse event in the middle of `target`.
+    fn mouse_event(
+        target: &Element,
+        event_type: &str,
+        button: i16,
+        buttons: u16,
+        detail: i32,
+    ) -> web_sys::MouseEvent {
+        let rect = target.get_bounding_client_rect();
+        let init = web_sys::MouseEventInit::new();
+        init.set_client_x((rect.left() + rect.width() / 2.0) as i32);
+        init.set_client_y((rect.top() + rect.height() / 2.0) as i32);
+        init.set_button(button);
+        init.set_buttons(buttons);
+        init.set_detail(detail);
+        init.set_bubbles(true);
+        init.set_cancelable(true);
+        web_sys::MouseEvent::new_with_mouse_event_init_dict(event_type, &init)
+            .expect("failed to create mouse event")
+    }
+
+    /// Creates a synthetic `wheel` event scrolling down.
+    fn wheel_event() -> web_sys::MouseEvent {
+        let init = web_sys::WheelEventInit::new();
+        init.set_delta_y(100.0);
+        init.set_cancelable(true);
+        web_sys::WheelEvent::new_with_event_init_dict("wheel", &init)
+            .expect("failed to create wheel event")
+            .into()
+    }
+
+    /// Dispatches mouse events to `target`, and checks the kinds `backend`
+    /// reports for them.
+    pub fn check<B: WebEventHandler>(backend: &mut B, target: &Element) {
+        use MouseButton::{Left, Right};
+        use MouseEventKind::*;
+
+        let cases = [
+            (mouse_event(target, "mousemove", 0, 0, 0), vec![Moved]),
+            (
+                mouse_event(target, "mousedown", 0, 1, 1),
+                vec![ButtonDown(Left)],
+            ),
+            (mouse_event(target, "mousemove", 0, 1, 0), vec![Drag(Left)]),
+            (mouse_event(target, "mousemove", 0, 2, 0), vec![Drag(Right)]),
+            (
+                mouse_event(target, "mouseup", 2, 0, 1),
+                vec![ButtonUp(Right)],
+            ),
+            (
+                mouse_event(target, "click", 0, 0, 1),
+                vec![SingleClick(Left)],
+            ),
+            (
+                mouse_event(target, "dblclick", 0, 0, 2),
+                vec![DoubleClick(Left)],
+            ),
+            (
+                mouse_event(target, "click", 0, 0, 3),
+                vec![SingleClick(Left), TripleClick(Left)],
+            ),
+            (mouse_event(target, "mouseenter", 0, 0, 0), vec![Entered]),
+            (mouse_event(target, "mouseleave", 0, 0, 0), vec![Exited]),
+            (wheel_event(), vec![ScrollDown]),
+        ];
+
+        let reported = Rc::new(RefCell::new(Vec::new()));
+        backend
+            .on_mouse_event({
+                let reported = reported.clone();
+                move |event| reported.borrow_mut().push(event.kind)
+            })
+            .expect("failed to set mouse event handler");
+
+        for (event, expected) in cases {
+            reported.borrow_mut().clear();
+            target
+                .dispatch_event(&event)
+                .expect("failed to dispatch event");
+            assert_eq!(*reported.borrow(), expected, "{}", event.type_());
+        }
     }
 }
 
@@ -213,11 +869,124 @@ mod tests {
     fn test_mouse_config_builder() {
         let config = MouseConfig::new(80, 24)
             .with_offset(5.0)
-            .with_cell_dimensions(10.0, 19.0);
+            .with_cell_dimensions(10.0, 19.0)
+            .with_prevent_default_scroll(true)
+            .with_prevent_default_touch(true);
 
         assert_eq!(config.grid_width, 80);
         assert_eq!(config.grid_height, 24);
         assert_eq!(config.offset, Some(5.0));
         assert_eq!(config.cell_dimensions, Some((10.0, 19.0)));
+        assert!(config.prevent_default_scroll);
+        assert!(config.prevent_default_touch);
+    }
+
+    #[test]
+    fn test_wheel_delta_to_kind() {
+        assert_eq!(
+            wheel_delta_to_kind(0.0, -100.0),
+            Some(MouseEventKind::ScrollUp)
+        );
+        assert_eq!(
+            wheel_delta_to_kind(0.0, 100.0),
+            Some(MouseEventKind::ScrollDown)
+        );
+        assert_eq!(
+            wheel_delta_to_kind(-3.0, 0.0),
+            Some(MouseEventKind::ScrollLeft)
+        );
+        assert_eq!(
+            wheel_delta_to_kind(3.0, 0.0),
+            Some(MouseEventKind::ScrollRight)
+        );
+
+        // dominant axis wins for diagonal trackpad gestures
+        assert_eq!(
+            wheel_delta_to_kind(2.0, -10.0),
+            Some(MouseEventKind::ScrollUp)
+        );
+        assert_eq!(
+            wheel_delta_to_kind(-10.0, 2.0),
+            Some(MouseEventKind::ScrollLeft)
+        );
+    }
+
+    #[test]
+    fn test_wheel_delta_to_kind_without_scroll() {
+        // e.g. a wheel event along the z axis only
+        assert_eq!(wheel_delta_to_kind(0.0, 0.0), None);
+    }
+
+    #[test]
+    fn test_locate_in_grid() {
+        let locate = |x, y| locate_in_grid((x, y), (800.0, 480.0), (80, 24), 2.0);
+
+        let (col, row, offset) = locate(25.0, 50.0);
+        assert_eq!((col, row), (2, 2));
+        let offset = offset.expect("offset");
+        assert_eq!(offset.css_px(), (5.0, 10.0));
+        assert_eq!(offset.device_px(), (10.0, 20.0));
+        assert_eq!(offset.cell_size_css_px(), (10.0, 20.0));
+
+        // positions past the edge are clamped to the last cell
+        let (col, row, offset) = locate(900.0, 500.0);
+        assert_eq!((col, row), (79, 23));
+        let offset = offset.expect("offset");
+        assert_eq!(offset.css_px(), (10.0, 20.0));
+
+        assert_eq!(
+            locate_in_grid((5.0, 5.0), (800.0, 480.0), (0, 0), 1.0),
+            (0, 0, None)
+        );
+    }
+
+    #[test]
+    fn test_held_button() {
+        assert_eq!(held_button(0), None);
+        assert_eq!(held_button(1), Some(MouseButton::Left));
+        assert_eq!(held_button(4), Some(MouseButton::Middle));
+        assert_eq!(held_button(16), Some(MouseButton::Forward));
+        assert_eq!(held_button(2 | 4), Some(MouseButton::Right));
+    }
+
+    #[test]
+    fn test_resize_callback() {
+        use std::{cell::RefCell, rc::Rc};
+
+        let size = |cols, rows| ResizeEvent {
+            cols,
+            rows,
+            width: cols * 10,
+            height: rows * 20,
+        };
+        let reported = Rc::new(RefCell::new(Vec::new()));
+        let mut callback = ResizeCallback::new(Some(size(80, 24)), {
+            let reported = reported.clone();
+            move |event| reported.borrow_mut().push(event)
+        });
+
+        callback.update(size(80, 24));
+        callback.update(size(100, 30));
+        callback.update(size(100, 30));
+        callback.update(size(80, 24));
+
+        assert_eq!(*reported.borrow(), vec![size(100, 30), size(80, 24)]);
+
+        // the first update only records the size if it wasn't known
+        reported.borrow_mut().clear();
+        let mut callback = ResizeCallback::new(None, {
+            let reported = reported.clone();
+            move |event| reported.borrow_mut().push(event)
+        });
+        callback.update(size(80, 24));
+        callback.update(size(100, 30));
+
+        assert_eq!(*reported.borrow(), vec![size(100, 30)]);
+    }
+
+    #[test]
+    fn test_normalize_line_endings() {
+        assert_eq!(normalize_line_endings("a\r\nb\rc\nd"), "a\nb\nc\nd");
+        assert_eq!(normalize_line_endings("no newline"), "no newline");
     }
 }
@@ -0,0 +1,272 @@
+//! Gamepad input handling.
+//!
+//! The Gamepad API has no events for buttons and axes, so
+//! [`GamepadCallback`] polls `navigator.getGamepads()` on every animation
+//! frame of the render loop, and reports the differences to the previous
+//! poll as [`GamepadEvent`]s.
+
+use web_sys::wasm_bindgen::JsCast;
+
+use crate::event::{GamepadAxis, GamepadButton, GamepadEvent, GamepadEventKind};
+
+/// The default dead zone of gamepad axes.
+pub(super) const DEFAULT_GAMEPAD_DEAD_ZONE: f64 = 0.1;
+
+/// The state of a gamepad at the time of a poll.
+#[derive(Debug, Clone, PartialEq)]
+struct GamepadSnapshot {
+    /// The name of the gamepad.
+    id: String,
+    /// Whether the gamepad uses the standard layout.
+    standard: bool,
+    /// Whether each button is pressed.
+    buttons: Vec<bool>,
+    /// The value of each axis, after applying the dead zone.
+    axes: Vec<f64>,
+}
+
+impl GamepadSnapshot {
+    /// Reads the state of a gamepad.
+    fn read(gamepad: &web_sys::Gamepad, dead_zone: f64) -> Self {
+        let buttons = gamepad
+            .buttons()
+            .iter()
+            .map(|button| {
+                button
+                    .dyn_into::<web_sys::GamepadButton>()
+                    .is_ok_and(|button| button.pressed())
+            })
+            .collect();
+        let axes = gamepad
+            .axes()
+            .iter()
+            .map(|axis| apply_dead_zone(axis.as_f64().unwrap_or_default(), dead_zone))
+            .collect();
+        Self {
+            id: gamepad.id(),
+            standard: gamepad.mapping() == web_sys::GamepadMappingType::Standard,
+            buttons,
+            axes,
+        }
+    }
+}
+
+/// Returns the value of an axis with the dead zone around the center removed,
+/// and the remaining range scaled to start at zero.
+fn apply_dead_zone(value: f64, dead_zone: f64) -> f64 {
+    let value = value.clamp(-1.0, 1.0);
+    if value.abs() <= dead_zone {
+        return 0.0;
+    }
+    value.signum() * (value.abs() - dead_zone) / (1.0 - dead_zone)
+}
+
+/// Polls the connected gamepads and reports changes to a callback.
+pub(super) struct GamepadCallback {
+    /// The state of each gamepad at the last poll, by index.
+    gamepads: Vec<Option<GamepadSnapshot>>,
+    /// The dead zone of the axes.
+    dead_zone: f64,
+    /// The callback to call with gamepad events.
+    callback: Box<dyn FnMut(GamepadEvent)>,
+}
+
+impl GamepadCallback {
+    /// Creates a new [`GamepadCallback`] with the given axis dead zone.
+    pub fn new<F>(dead_zone: f64, callback: F) -> Self
+    where
+        F: FnMut(GamepadEvent) + 'static,
+    {
+        Self {
+            gamepads: Vec::new(),
+            dead_zone: dead_zone.clamp(0.0, 0.99),
+            callback: Box::new(callback),
+        }
+    }
+
+    /// Polls the gamepads, calling the callback for every change since the
+    /// last poll, and returns whether there were any changes.
+    pub fn poll(&mut self) -> bool {
+        let Some(gamepads) = web_sys::window().and_then(|w| w.navigator().get_gamepads().ok())
+        else {
+            return false;
+        };
+        let snapshots = gamepads
+            .iter()
+            .map(|gamepad| {
+                gamepad
+                    .dyn_into::<web_sys::Gamepad>()
+                    .ok()
+                    .filter(|gamepad| gamepad.connected())
+                    .map(|gamepad| GamepadSnapshot::read(&gamepad, self.dead_zone))
+            })
+            .collect();
+        self.update(snapshots)
+    }
+
+    /// Records the state of the gamepads, calling the callback for every
+    /// change since the last update, and returns whether there were any
+    /// changes.
+    fn update(&mut self, snapshots: Vec<Option<GamepadSnapshot>>) -> bool {
+        let count = snapshots.len().max(self.gamepads.len());
+        let previous = std::mem::replace(&mut self.gamepads, snapshots);
+        let mut changed = false;
+        for index in 0..count {
+            let old = previous.get(index).and_then(Option::as_ref);
+            let new = self.gamepads.get(index).and_then(Option::as_ref);
+            for kind in changes(old, new) {
+                changed = true;
+                (self.callback)(GamepadEvent {
+                    gamepad: index as u32,
+                    kind,
+                });
+            }
+        }
+        changed
+    }
+}
+
+/// Returns the changes between two states of a gamepad.
+///
+/// A gamepad that was reconnected with a different name is reported as
+/// disconnected and connected again.
+fn changes(old: Option<&GamepadSnapshot>, new: Option<&GamepadSnapshot>) -> Vec<GamepadEventKind> {
+    let mut changes = Vec::new();
+    let old = match (old, new) {
+        (Some(old), Some(new)) if old.id != new.id => {
+            changes.push(GamepadEventKind::Disconnected);
+            None
+        }
+        (old, _) => old,
+    };
+    let Some(new) = new else {
+        if old.is_some() {
+            changes.push(GamepadEventKind::Disconnected);
+        }
+        return changes;
+    };
+    if old.is_none() {
+        changes.push(GamepadEventKind::Connected {
+            id: new.id.clone(),
+            standard: new.standard,
+        });
+    }
+
+    for (index, pressed) in new.buttons.iter().enumerate() {
+        let was_pressed = old.and_then(|old| old.buttons.get(index)) == Some(&true);
+        if *pressed != was_pressed {
+            let button = GamepadButton::from_index(index as u32, new.standard);
+            changes.push(if *pressed {
+                GamepadEventKind::ButtonPressed(button)
+            } else {
+                GamepadEventKind::ButtonReleased(button)
+            });
+        }
+    }
+    for (index, value) in new.axes.iter().enumerate() {
+        let old_value = old.and_then(|old| old.axes.get(index)).unwrap_or(&0.0);
+        if value != old_value {
+            let axis = GamepadAxis::from_index(index as u32, new.standard);
+            changes.push(GamepadEventKind::AxisChanged(axis, *value));
+        }
+    }
+    changes
+}
+
+impl std::fmt::Debug for GamepadCallback {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GamepadCallback")
+            .field("gamepads", &self.gamepads)
+            .field("dead_zone", &self.dead_zone)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+
+    fn snapshot(id: &str, buttons: &[bool], axes: &[f64]) -> Option<GamepadSnapshot> {
+        Some(GamepadSnapshot {
+            id: id.to_string(),
+            standard: true,
+            buttons: buttons.to_vec(),
+            axes: axes.to_vec(),
+        })
+    }
+
+    #[test]
+    fn test_apply_dead_zone() {
+        assert_eq!(apply_dead_zone(0.05, 0.1), 0.0);
+        assert_eq!(apply_dead_zone(-0.1, 0.1), 0.0);
+        assert_eq!(apply_dead_zone(1.0, 0.1), 1.0);
+        assert_eq!(apply_dead_zone(-1.5, 0.1), -1.0);
+        assert!((apply_dead_zone(0.55, 0.1) - 0.5).abs() < 1e-9);
+        assert_eq!(apply_dead_zone(0.3, 0.0), 0.3);
+    }
+
+    #[test]
+    fn test_gamepad_changes() {
+        let events = Rc::new(RefCell::new(Vec::new()));
+        let mut callback = GamepadCallback::new(DEFAULT_GAMEPAD_DEAD_ZONE, {
+            let events = events.clone();
+            move |event| events.borrow_mut().push(event)
+        });
+        let mut update = |gamepads| {
+            let changed = callback.update(gamepads);
+            let events = std::mem::take(&mut *events.borrow_mut());
+            assert_eq!(changed, !events.is_empty());
+            events
+        };
+        let event = |gamepad, kind| GamepadEvent { gamepad, kind };
+
+        assert_eq!(update(vec![None]), []);
+        assert_eq!(
+            update(vec![None, snapshot("pad", &[true, false], &[0.0, 0.0])]),
+            [
+                event(
+                    1,
+                    GamepadEventKind::Connected {
+                        id: "pad".to_string(),
+                        standard: true,
+                    }
+                ),
+                event(1, GamepadEventKind::ButtonPressed(GamepadButton::South)),
+            ]
+        );
+        assert_eq!(
+            update(vec![None, snapshot("pad", &[false, true], &[0.0, -0.5])]),
+            [
+                event(1, GamepadEventKind::ButtonReleased(GamepadButton::South)),
+                event(1, GamepadEventKind::ButtonPressed(GamepadButton::East)),
+                event(
+                    1,
+                    GamepadEventKind::AxisChanged(GamepadAxis::LeftStickY, -0.5)
+                ),
+            ]
+        );
+        assert_eq!(
+            update(vec![None, snapshot("pad", &[false, true], &[0.0, -0.5])]),
+            []
+        );
+        assert_eq!(
+            update(vec![None, None]),
+            [event(1, GamepadEventKind::Disconnected)]
+        );
+        assert_eq!(update(vec![]), []);
+    }
+
+    #[test]
+    fn test_gamepad_layout() {
+        assert_eq!(GamepadButton::from_index(3, true), GamepadButton::North);
+        assert_eq!(GamepadButton::from_index(3, false), GamepadButton::Other(3));
+        assert_eq!(
+            GamepadButton::from_index(17, true),
+            GamepadButton::Other(17)
+        );
+        assert_eq!(GamepadAxis::from_index(2, true), GamepadAxis::RightStickX);
+        assert_eq!(GamepadAxis::from_index(2, false), GamepadAxis::Other(2));
+    }
+}
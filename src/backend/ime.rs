@@ -0,0 +1,400 @@
+//! Hidden text input for IME composition.
+//!
+//! Browsers only dispatch composition events to editable elements, so the
+//! `<div>` and `<canvas>` elements used by the backends never see them.
+//! [`ImeInput`] places a transparent `<textarea>` over the terminal cursor
+//! which takes over keyboard focus from the terminal element. Composition
+//! events are reported as [`ImeEvent`]s, and all other key and paste events
+//! are forwarded to the terminal element so that their handlers keep working.
+
+use std::{cell::RefCell, rc::Rc};
+
+use ratatui::layout::Position;
+use web_sys::{wasm_bindgen::JsCast, Element, HtmlTextAreaElement};
+
+use crate::{
+    backend::{event_callback::EventCallback, utils::get_document},
+    error::Error,
+    event::ImeEvent,
+};
+
+/// The event types for composition events.
+const COMPOSITION_EVENT_TYPES: &[&str] = &["compositionstart", "compositionend"];
+
+/// The event types for input events.
+const INPUT_EVENT_TYPES: &[&str] = &["input"];
+
+/// The event types for key events that are forwarded to the terminal element.
+const FORWARDED_KEY_EVENT_TYPES: &[&str] = &["keydown", "keyup"];
+
+/// The event types for paste events that are forwarded to the terminal element.
+const FORWARDED_PASTE_EVENT_TYPES: &[&str] = &["paste"];
+
+/// The event types for focus events on the terminal element.
+const FOCUS_EVENT_TYPES: &[&str] = &["focus"];
+
+/// The attribute that marks the hidden textarea.
+const IME_INPUT_ATTRIBUTE: &str = "data-ratzilla-ime";
+
+/// The `keyCode` reported for key events that are consumed by an IME.
+const IME_PROCESS_KEY_CODE: u32 = 229;
+
+/// Where to place the IME candidate window.
+#[derive(Debug, Clone, Copy)]
+struct ImeAnchor {
+    /// The terminal cursor position, in cells.
+    cursor: Position,
+    /// Cell dimensions in CSS pixels (width, height).
+    cell_size: (f64, f64),
+    /// Pixel offset from the terminal element edge.
+    offset: f64,
+}
+
+/// The terminal element and its IME input.
+///
+/// Backends share this with the event callbacks that have to recognize the
+/// terminal, so that they keep working when the backend replaces its element
+/// or the IME input is added or removed.
+#[derive(Debug, Clone)]
+pub(super) struct TerminalElements(Rc<RefCell<TerminalElementsState>>);
+
+/// The state of [`TerminalElements`].
+#[derive(Debug)]
+struct TerminalElementsState {
+    /// The terminal element.
+    element: Element,
+    /// The hidden textarea of the IME input, if any.
+    ime_input: Option<Element>,
+}
+
+impl TerminalElements {
+    /// Creates a new [`TerminalElements`] for the terminal element.
+    pub fn new(element: Element) -> Self {
+        Self(Rc::new(RefCell::new(TerminalElementsState {
+            element,
+            ime_input: None,
+        })))
+    }
+
+    /// Returns the terminal element.
+    pub fn element(&self) -> Element {
+        self.0.borrow().element.clone()
+    }
+
+    /// Replaces the terminal element.
+    pub fn set_element(&self, element: Element) {
+        self.0.borrow_mut().element = element;
+    }
+
+    /// Returns whether `node` is the IME input of the terminal.
+    pub fn is_ime_input(&self, node: &Element) -> bool {
+        self.0.borrow().ime_input.as_ref() == Some(node)
+    }
+
+    /// Returns whether `node` is the terminal element or its IME input.
+    pub fn contains(&self, node: &Element) -> bool {
+        &self.0.borrow().element == node || self.is_ime_input(node)
+    }
+}
+
+/// The callback for IME events, shared with the [`ImeInput`] that replaces
+/// it when the terminal element changes.
+type SharedImeCallback = Rc<RefCell<dyn FnMut(ImeEvent)>>;
+
+/// A hidden `<textarea>` that receives IME composition on behalf of the
+/// terminal element.
+///
+/// The textarea is removed from the document when dropped.
+pub(super) struct ImeInput {
+    /// The hidden textarea.
+    textarea: HtmlTextAreaElement,
+    /// The terminal element.
+    element: Element,
+    /// The terminal elements the textarea is registered with.
+    elements: TerminalElements,
+    /// The IME event callback.
+    callback: SharedImeCallback,
+    /// Where to place the candidate window.
+    anchor: Rc<RefCell<ImeAnchor>>,
+    /// Redirects focus from the terminal element to the textarea.
+    _focus_callback: EventCallback<web_sys::Event>,
+    /// Forwards key events to the terminal element.
+    _key_callback: EventCallback<web_sys::KeyboardEvent>,
+    /// Forwards paste events to the terminal element.
+    _paste_callback: EventCallback<web_sys::ClipboardEvent>,
+    /// Composition start and end handler.
+    _composition_callback: EventCallback<web_sys::CompositionEvent>,
+    /// Composition update handler.
+    _input_callback: EventCallback<web_sys::InputEvent>,
+}
+
+impl std::fmt::Debug for ImeInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImeInput")
+            .field("textarea", &self.textarea)
+            .field("element", &self.element)
+            .field("anchor", &self.anchor)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ImeInput {
+    /// Creates a new [`ImeInput`] for the terminal element.
+    ///
+    /// The textarea is appended to `container`, which should be the element
+    /// the terminal element is placed in, and takes focus whenever the
+    /// terminal element would. It is registered as the IME input of
+    /// `elements` until dropped.
+    pub fn new<F>(
+        elements: &TerminalElements,
+        container: &Element,
+        cell_size: (f64, f64),
+        offset: f64,
+        callback: F,
+    ) -> Result<Self, Error>
+    where
+        F: FnMut(ImeEvent) + 'static,
+    {
+        let anchor = ImeAnchor {
+            cursor: Position::ORIGIN,
+            cell_size,
+            offset,
+        };
+        Self::with_callback(elements, container, anchor, Rc::new(RefCell::new(callback)))
+    }
+
+    /// Creates a new [`ImeInput`] with the same callback and cursor position
+    /// for the current terminal element, e.g. after the backend replaced it.
+    pub fn rebind(&self, container: &Element) -> Result<Self, Error> {
+        let anchor = *self.anchor.borrow();
+        Self::with_callback(&self.elements, container, anchor, self.callback.clone())
+    }
+
+    /// Returns the terminal element the textarea takes focus for.
+    pub fn element(&self) -> &Element {
+        &self.element
+    }
+
+    /// Returns the textarea.
+    pub fn textarea(&self) -> Element {
+        self.textarea.clone().into()
+    }
+
+    /// Creates the textarea and its event callbacks.
+    fn with_callback(
+        elements: &TerminalElements,
+        container: &Element,
+        anchor: ImeAnchor,
+        callback: SharedImeCallback,
+    ) -> Result<Self, Error> {
+        let element = &elements.element();
+        let document = get_document()?;
+        let textarea: HtmlTextAreaElement = document
+            .create_element("textarea")?
+            .dyn_into()
+            .map_err(|_| Error::UnableToRetrieveComponent("textarea"))?;
+        for (name, value) in [
+            ("tabindex", "-1"),
+            ("autocomplete", "off"),
+            ("autocorrect", "off"),
+            ("autocapitalize", "off"),
+            ("spellcheck", "false"),
+            ("aria-hidden", "true"),
+            (IME_INPUT_ATTRIBUTE, ""),
+        ] {
+            textarea.set_attribute(name, value)?;
+        }
+
+        let anchor = Rc::new(RefCell::new(anchor));
+        textarea.set_attribute("style", &textarea_style(element, &anchor.borrow()))?;
+        container.append_child(&textarea)?;
+        elements.0.borrow_mut().ime_input = Some(textarea.clone().into());
+
+        let focus_callback = EventCallback::new(element.clone(), FOCUS_EVENT_TYPES, {
+            let textarea = textarea.clone();
+            move |_: web_sys::Event| {
+                let _ = textarea.focus();
+            }
+        })?;
+
+        let key_callback = EventCallback::new(textarea.clone(), FORWARDED_KEY_EVENT_TYPES, {
+            let element = element.clone();
+            move |event: web_sys::KeyboardEvent| {
+                if event.is_composing() || event.key_code() == IME_PROCESS_KEY_CODE {
+                    return;
+                }
+                if let Ok(forwarded) = forward_key_event(&event) {
+                    if let Ok(false) = element.dispatch_event(&forwarded) {
+                        event.prevent_default();
+                    }
+                }
+            }
+        })?;
+
+        let paste_callback = EventCallback::new(textarea.clone(), FORWARDED_PASTE_EVENT_TYPES, {
+            let element = element.clone();
+            move |event: web_sys::ClipboardEvent| {
+                // Pasted text must not end up in the textarea
+                event.prevent_default();
+                if let Ok(forwarded) = forward_paste_event(&event) {
+                    let _ = element.dispatch_event(&forwarded);
+                }
+            }
+        })?;
+
+        let composition_callback =
+            EventCallback::new(textarea.clone(), COMPOSITION_EVENT_TYPES, {
+                let textarea = textarea.clone();
+                let element = element.clone();
+                let anchor = anchor.clone();
+                let callback = callback.clone();
+                move |event: web_sys::CompositionEvent| {
+                    if event.type_() == "compositionstart" {
+                        textarea.set_value("");
+                        let _ = textarea
+                            .set_attribute("style", &textarea_style(&element, &anchor.borrow()));
+                    } else {
+                        textarea.set_value("");
+                        if let Ok(mut callback) = callback.try_borrow_mut() {
+                            callback(ImeEvent::Commit(event.data().unwrap_or_default()));
+                        }
+                    }
+                }
+            })?;
+
+        let input_callback = EventCallback::new(textarea.clone(), INPUT_EVENT_TYPES, {
+            let textarea = textarea.clone();
+            let callback = callback.clone();
+            move |event: web_sys::InputEvent| {
+                if !event.is_composing() {
+                    // Text typed without composition is reported via key events
+                    textarea.set_value("");
+                    return;
+                }
+                let preedit = textarea.value();
+                let cursor = textarea
+                    .selection_end()
+                    .ok()
+                    .flatten()
+                    .map(|end| utf16_to_char_index(&preedit, end as usize));
+                if let Ok(mut callback) = callback.try_borrow_mut() {
+                    callback(ImeEvent::Composition { preedit, cursor });
+                }
+            }
+        })?;
+
+        Ok(Self {
+            textarea,
+            element: element.clone(),
+            elements: elements.clone(),
+            callback,
+            anchor,
+            _focus_callback: focus_callback,
+            _key_callback: key_callback,
+            _paste_callback: paste_callback,
+            _composition_callback: composition_callback,
+            _input_callback: input_callback,
+        })
+    }
+
+    /// Sets the terminal cursor position and the cell dimensions in CSS
+    /// pixels, which are used to place the candidate window when the next
+    /// composition starts.
+    pub fn set_cursor_position(&self, position: Position, cell_size: (f64, f64)) {
+        let mut anchor = self.anchor.borrow_mut();
+        anchor.cursor = position;
+        anchor.cell_size = cell_size;
+    }
+}
+
+impl Drop for ImeInput {
+    fn drop(&mut self) {
+        self.textarea.remove();
+        // A replacement may have been registered already
+        let mut elements = self.elements.0.borrow_mut();
+        if elements.ime_input.as_ref() == Some(self.textarea.as_ref()) {
+            elements.ime_input = None;
+        }
+    }
+}
+
+/// Returns the inline style placing the textarea over the terminal cursor.
+///
+/// The textarea is positioned relative to the viewport, so the position is
+/// recomputed at the start of every composition in case the page scrolled.
+fn textarea_style(element: &Element, anchor: &ImeAnchor) -> String {
+    let rect = element.get_bounding_client_rect();
+    let (cell_width, cell_height) = anchor.cell_size;
+    let left = rect.left() + anchor.offset + anchor.cursor.x as f64 * cell_width;
+    let top = rect.top() + anchor.offset + anchor.cursor.y as f64 * cell_height;
+    format!(
+        "position: fixed; left: {left}px; top: {top}px; width: 1px; height: {cell_height}px; \
+         font-size: {cell_height}px; padding: 0; border: 0; margin: 0; outline: none; \
+         resize: none; overflow: hidden; opacity: 0; color: transparent; \
+         background: transparent; caret-color: transparent; pointer-events: none;"
+    )
+}
+
+/// Creates a copy of a key event that can be dispatched to another element.
+///
+/// Browsers don't allow re-dispatching an event that is still being
+/// dispatched, so the event is recreated from its properties.
+fn forward_key_event(event: &web_sys::KeyboardEvent) -> Result<web_sys::KeyboardEvent, Error> {
+    let init = web_sys::KeyboardEventInit::new();
+    init.set_key(&event.key());
+    init.set_code(&event.code());
+    init.set_location(event.location());
+    init.set_repeat(event.repeat());
+    init.set_ctrl_key(event.ctrl_key());
+    init.set_alt_key(event.alt_key());
+    init.set_shift_key(event.shift_key());
+    init.set_meta_key(event.meta_key());
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    Ok(web_sys::KeyboardEvent::new_with_keyboard_event_init_dict(
+        &event.type_(),
+        &init,
+    )?)
+}
+
+/// Creates a copy of a paste event that can be dispatched to another element.
+fn forward_paste_event(event: &web_sys::ClipboardEvent) -> Result<web_sys::ClipboardEvent, Error> {
+    let init = web_sys::ClipboardEventInit::new();
+    init.set_clipboard_data(event.clipboard_data().as_ref());
+    init.set_bubbles(true);
+    init.set_cancelable(true);
+    Ok(web_sys::ClipboardEvent::new_with_event_init_dict(
+        &event.type_(),
+        &init,
+    )?)
+}
+
+/// Converts an offset in UTF-16 code units, as used by the DOM, to an
+/// offset in chars.
+fn utf16_to_char_index(text: &str, utf16_offset: usize) -> usize {
+    let mut utf16_len = 0;
+    text.chars()
+        .take_while(|c| {
+            utf16_len += c.len_utf16();
+            utf16_len <= utf16_offset
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_utf16_to_char_index() {
+        assert_eq!(utf16_to_char_index("", 0), 0);
+        assert_eq!(utf16_to_char_index("かんじ", 0), 0);
+        assert_eq!(utf16_to_char_index("かんじ", 2), 2);
+        assert_eq!(utf16_to_char_index("かんじ", 3), 3);
+        // surrogate pairs count as a single char
+        assert_eq!(utf16_to_char_index("𠮷野家", 2), 1);
+        assert_eq!(utf16_to_char_index("𠮷野家", 4), 3);
+        // out of range offsets are clamped
+        assert_eq!(utf16_to_char_index("abc", 10), 3);
+    }
+}
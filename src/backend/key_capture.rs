@@ -0,0 +1,113 @@
+//! Policies for which key events have their default browser action
+//! prevented.
+
+use std::{fmt, rc::Rc};
+
+use crate::event::{KeyCode, KeyEvent};
+
+/// Policy for which key events have their default browser action prevented.
+///
+/// By default, key events are delivered to the handler and then also handled
+/// by the browser: Tab moves the focus away from the terminal, Space scrolls
+/// the page, Ctrl+F opens the search bar and so on. Keys captured by the
+/// policy have `preventDefault` called on them. They are delivered to the
+/// handler either way.
+///
+/// ```
+/// use ratzilla::{event::KeyCode, KeyCapture};
+///
+/// // Keep Tab and Space inside the terminal
+/// let keys = KeyCapture::keys([KeyCode::Tab, KeyCode::Char(' ')]);
+///
+/// // Take over Ctrl+F and all function keys
+/// let shortcuts = KeyCapture::predicate(|key| {
+///     matches!(key.code, KeyCode::F(_)) || (key.ctrl && key.code == KeyCode::Char('f'))
+/// });
+/// ```
+///
+/// Capturing Ctrl+C, Ctrl+V or Ctrl+X also prevents copying and pasting with
+/// the keyboard, so that no paste events are received for them.
+#[derive(Clone, Default)]
+pub enum KeyCapture {
+    /// Don't prevent the default action of any key.
+    #[default]
+    None,
+    /// Prevent the default action of all keys.
+    All,
+    /// Prevent the default action of the given keys, regardless of modifiers.
+    Keys(Vec<KeyCode>),
+    /// Prevent the default action of the keys the predicate returns `true` for.
+    Predicate(Rc<dyn Fn(&KeyEvent) -> bool>),
+}
+
+impl KeyCapture {
+    /// Constructs a [`KeyCapture::Keys`] policy from the given keys.
+    pub fn keys(keys: impl IntoIterator<Item = KeyCode>) -> Self {
+        Self::Keys(keys.into_iter().collect())
+    }
+
+    /// Constructs a [`KeyCapture::Predicate`] policy from the given predicate.
+    pub fn predicate<F>(predicate: F) -> Self
+    where
+        F: Fn(&KeyEvent) -> bool + 'static,
+    {
+        Self::Predicate(Rc::new(predicate))
+    }
+
+    /// Returns whether the default action of the key event should be prevented.
+    pub fn captures(&self, event: &KeyEvent) -> bool {
+        match self {
+            Self::None => false,
+            Self::All => true,
+            Self::Keys(keys) => keys.contains(&event.code),
+            Self::Predicate(predicate) => predicate(event),
+        }
+    }
+}
+
+impl fmt::Debug for KeyCapture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => f.write_str("None"),
+            Self::All => f.write_str("All"),
+            Self::Keys(keys) => f.debug_tuple("Keys").field(keys).finish(),
+            Self::Predicate(_) => f.write_str("Predicate(..)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::KeyEventKind;
+
+    fn key(code: KeyCode, ctrl: bool) -> KeyEvent {
+        KeyEvent {
+            code,
+            physical_key: None,
+            ctrl,
+            alt: false,
+            shift: false,
+            meta: false,
+            kind: KeyEventKind::Press,
+        }
+    }
+
+    #[test]
+    fn test_key_capture() {
+        let tab = key(KeyCode::Tab, false);
+        let ctrl_f = key(KeyCode::Char('f'), true);
+
+        assert!(!KeyCapture::default().captures(&tab));
+        assert!(KeyCapture::All.captures(&ctrl_f));
+
+        let keys = KeyCapture::keys([KeyCode::Tab, KeyCode::Char(' ')]);
+        assert!(keys.captures(&tab));
+        assert!(keys.captures(&key(KeyCode::Char(' '), true)));
+        assert!(!keys.captures(&ctrl_f));
+
+        let predicate = KeyCapture::predicate(|key| key.ctrl);
+        assert!(predicate.captures(&ctrl_f));
+        assert!(!predicate.captures(&tab));
+    }
+}
@@ -30,7 +30,7 @@
 //! | **Underline**                | ✓          | ✗             | ✓              |
 //! | **Strikethrough**            | ✓          | ✗             | ✓              |
 //! | **Browser Support**          | All        | All           | Modern (2017+) |
-//! | **Mouse Events**             | Full       | Full          | Basic          |
+//! | **Mouse Events**             | Full       | Full          | Full           |
 //!
 //! ¹: The [dynamic font atlas](webgl2::FontAtlasConfig::Dynamic) rasterizes
 //!    glyphs on demand with full Unicode/emoji and font variant support. The
@@ -48,10 +48,16 @@
 //! | `Moved`         | ✓          | ✓             | ✓             |
 //! | `ButtonDown`    | ✓          | ✓             | ✓             |
 //! | `ButtonUp`      | ✓          | ✓             | ✓             |
-//! | `SingleClick`   | ✓          | ✓             | ✗             |
-//! | `DoubleClick`   | ✓          | ✓             | ✗             |
-//! | `Entered`       | ✓          | ✓             | ✗             |
-//! | `Exited`        | ✓          | ✓             | ✗             |
+//! | `Drag`          | ✓          | ✓             | ✓             |
+//! | `SingleClick`   | ✓          | ✓             | ✓             |
+//! | `DoubleClick`   | ✓          | ✓             | ✓             |
+//! | `TripleClick`   | ✓          | ✓             | ✓             |
+//! | `Entered`       | ✓          | ✓             | ✓             |
+//! | `Exited`        | ✓          | ✓             | ✓             |
+//! | `Scroll*`       | ✓          | ✓             | ✓             |
+//!
+//! Touch input is reported as the same events: a tap is a left click, a long
+//! press a right click, and dragging a finger a drag with the left button.
 //!
 //! [`WebEventHandler`]: crate::WebEventHandler
 //!
@@ -74,6 +80,12 @@ pub mod webgl2;
 mod color;
 /// Event callback management.
 pub(super) mod event_callback;
+/// Gamepad input polling.
+mod gamepad;
+/// Hidden text input for IME composition.
+mod ime;
+/// Touch input and gesture recognition.
+mod touch;
 /// Backend utilities.
 pub(crate) mod utils;
 
@@ -81,3 +93,7 @@ pub(crate) mod utils;
 pub mod cell_sized;
 /// Cursor shapes.
 pub mod cursor;
+/// Keyboard capture policies.
+pub mod key_capture;
+/// Mouse cursor styles.
+pub mod mouse_cursor;
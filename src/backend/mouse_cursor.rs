@@ -0,0 +1,291 @@
+//! Mouse cursor styles for regions of the terminal grid, updated as the
+//! pointer moves over it.
+
+use std::{cell::RefCell, rc::Rc};
+
+use ratatui::layout::{Position, Rect};
+use web_sys::{wasm_bindgen::JsCast, Element};
+
+use crate::{
+    backend::event_callback::{mouse_to_grid_coords, EventCallback, MouseConfig},
+    error::Error,
+};
+
+/// The event types for tracking the pointer over the terminal.
+const POINTER_EVENT_TYPES: &[&str] = &["mousemove", "mouseleave"];
+
+/// A mouse cursor style.
+///
+/// Each variant corresponds to a value of the CSS
+/// [`cursor`](https://developer.mozilla.org/en-US/docs/Web/CSS/cursor) property.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum MouseCursor {
+    /// The platform default cursor, usually an arrow.
+    #[default]
+    Default,
+    /// No cursor is shown.
+    None,
+    /// A pointing hand, for links and buttons.
+    Pointer,
+    /// A text selection cursor.
+    Text,
+    /// A crosshair, for precise selection.
+    Crosshair,
+    /// Something can be moved.
+    Move,
+    /// Something can be grabbed, e.g. a draggable pane.
+    Grab,
+    /// Something is being grabbed.
+    Grabbing,
+    /// The action is not allowed.
+    NotAllowed,
+    /// The program is busy.
+    Wait,
+    /// Help is available.
+    Help,
+    /// A column can be resized horizontally, e.g. a vertical splitter.
+    ColResize,
+    /// A row can be resized vertically, e.g. a horizontal splitter.
+    RowResize,
+    /// An edge can be moved left or right.
+    EwResize,
+    /// An edge can be moved up or down.
+    NsResize,
+    /// A corner can be moved towards the top-right or bottom-left.
+    NeswResize,
+    /// A corner can be moved towards the top-left or bottom-right.
+    NwseResize,
+}
+
+impl MouseCursor {
+    /// Returns the value of the CSS `cursor` property for this cursor.
+    pub fn css_value(&self) -> &'static str {
+        match self {
+            MouseCursor::Default => "default",
+            MouseCursor::None => "none",
+            MouseCursor::Pointer => "pointer",
+            MouseCursor::Text => "text",
+            MouseCursor::Crosshair => "crosshair",
+            MouseCursor::Move => "move",
+            MouseCursor::Grab => "grab",
+            MouseCursor::Grabbing => "grabbing",
+            MouseCursor::NotAllowed => "not-allowed",
+            MouseCursor::Wait => "wait",
+            MouseCursor::Help => "help",
+            MouseCursor::ColResize => "col-resize",
+            MouseCursor::RowResize => "row-resize",
+            MouseCursor::EwResize => "ew-resize",
+            MouseCursor::NsResize => "ns-resize",
+            MouseCursor::NeswResize => "nesw-resize",
+            MouseCursor::NwseResize => "nwse-resize",
+        }
+    }
+}
+
+/// The mouse cursors to show over regions of the terminal.
+///
+/// This is a handle that can be cloned into the render callback, so that the
+/// regions can be updated along with the frame that draws them:
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use ratzilla::{
+///     ratatui::{layout::Rect, Terminal},
+///     DomBackend, MouseCursor, WebRenderer,
+/// };
+///
+/// let mut terminal = Terminal::new(DomBackend::new()?)?;
+/// let cursors = terminal.backend_mut().mouse_cursors()?;
+///
+/// terminal.draw_web(move |frame| {
+///     let button = Rect::new(2, 1, 10, 3);
+///     // render the button...
+///
+///     cursors.clear();
+///     cursors.set_region(button, MouseCursor::Pointer);
+/// });
+/// # Ok(())
+/// # }
+/// ```
+///
+/// The cursor is updated when the pointer moves, and after every frame.
+#[derive(Debug, Clone, Default)]
+pub struct MouseCursors {
+    /// The shared cursor regions.
+    inner: Rc<RefCell<CursorRegions>>,
+}
+
+/// The cursor regions shared by the [`MouseCursors`] handles.
+#[derive(Debug, Default)]
+struct CursorRegions {
+    /// The cursor shown outside of the regions.
+    default: MouseCursor,
+    /// The regions, in the order they were added.
+    regions: Vec<(Rect, MouseCursor)>,
+}
+
+impl MouseCursors {
+    /// Sets the cursor shown over the parts of the terminal that aren't
+    /// covered by a region.
+    pub fn set_default(&self, cursor: MouseCursor) {
+        self.inner.borrow_mut().default = cursor;
+    }
+
+    /// Sets the cursor shown over the given area of cells.
+    ///
+    /// Regions added later take precedence where regions overlap.
+    pub fn set_region(&self, area: Rect, cursor: MouseCursor) {
+        self.inner.borrow_mut().regions.push((area, cursor));
+    }
+
+    /// Removes all regions, and resets the default cursor.
+    pub fn clear(&self) {
+        let mut inner = self.inner.borrow_mut();
+        inner.default = MouseCursor::Default;
+        inner.regions.clear();
+    }
+
+    /// Returns the cursor shown over the given cell.
+    pub fn cursor_at(&self, col: u16, row: u16) -> MouseCursor {
+        let inner = self.inner.borrow();
+        inner
+            .regions
+            .iter()
+            .rev()
+            .find(|(area, _)| area.contains(Position::new(col, row)))
+            .map_or(inner.default, |(_, cursor)| *cursor)
+    }
+}
+
+/// Applies the cursors of a [`MouseCursors`] handle to the terminal element.
+#[derive(Debug)]
+pub(super) struct MouseCursorCallback {
+    /// The cursor regions.
+    cursors: MouseCursors,
+    /// The terminal element.
+    element: Element,
+    /// The state shared with the event listeners.
+    state: Rc<RefCell<PointerState>>,
+    /// The pointer event listeners.
+    _callback: EventCallback<web_sys::MouseEvent>,
+}
+
+/// The state of the pointer over the terminal.
+#[derive(Debug)]
+struct PointerState {
+    /// The coordinate translation for the current grid size.
+    config: MouseConfig,
+    /// The cell under the pointer, if it is over the terminal.
+    cell: Option<(u16, u16)>,
+    /// A cursor set by the backend, which takes precedence over the regions.
+    override_cursor: Option<MouseCursor>,
+    /// The cursor that was last applied.
+    applied: Option<MouseCursor>,
+}
+
+impl PointerState {
+    /// Applies the cursor for the cell under the pointer to the element.
+    fn apply(&mut self, element: &Element, cursors: &MouseCursors) {
+        let Some((col, row)) = self.cell else {
+            return;
+        };
+        let cursor = self
+            .override_cursor
+            .unwrap_or_else(|| cursors.cursor_at(col, row));
+        if self.applied != Some(cursor) {
+            self.applied = Some(cursor);
+            set_css_cursor(element, cursor.css_value());
+        }
+    }
+}
+
+impl MouseCursorCallback {
+    /// Creates a new [`MouseCursorCallback`] that updates the cursor of the
+    /// element as the pointer moves.
+    pub fn new(
+        element: Element,
+        config: MouseConfig,
+        cursors: MouseCursors,
+    ) -> Result<Self, Error> {
+        let state = Rc::new(RefCell::new(PointerState {
+            config,
+            cell: None,
+            override_cursor: None,
+            applied: None,
+        }));
+        let callback = EventCallback::new(element.clone(), POINTER_EVENT_TYPES, {
+            let (cursors, element, state) = (cursors.clone(), element.clone(), state.clone());
+            move |event: web_sys::MouseEvent| {
+                let mut state = state.borrow_mut();
+                if event.type_() == "mouseleave" {
+                    state.cell = None;
+                    return;
+                }
+                state.cell = Some(mouse_to_grid_coords(&event, &element, &state.config));
+                state.apply(&element, &cursors);
+            }
+        })?;
+        Ok(Self {
+            cursors,
+            element,
+            state,
+            _callback: callback,
+        })
+    }
+
+    /// Returns the element the cursor is applied to.
+    pub fn element(&self) -> &Element {
+        &self.element
+    }
+
+    /// Returns a handle to the cursor regions.
+    pub fn cursors(&self) -> MouseCursors {
+        self.cursors.clone()
+    }
+
+    /// Sets a cursor that takes precedence over the regions, e.g. while the
+    /// pointer is over a hyperlink.
+    pub fn set_override(&self, cursor: Option<MouseCursor>) {
+        let mut state = self.state.borrow_mut();
+        state.override_cursor = cursor;
+        state.apply(&self.element, &self.cursors);
+    }
+
+    /// Updates the coordinate translation, and applies the cursor for the
+    /// cell under the pointer, as the regions may have changed.
+    pub fn refresh(&self, config: MouseConfig) {
+        let mut state = self.state.borrow_mut();
+        state.config = config;
+        state.apply(&self.element, &self.cursors);
+    }
+}
+
+/// Sets the CSS `cursor` property of the element.
+pub(super) fn set_css_cursor(element: &Element, value: &str) {
+    if let Some(element) = element.dyn_ref::<web_sys::HtmlElement>() {
+        let _ = element.style().set_property("cursor", value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_regions() {
+        let cursors = MouseCursors::default();
+        assert_eq!(cursors.cursor_at(0, 0), MouseCursor::Default);
+
+        cursors.set_default(MouseCursor::Text);
+        cursors.set_region(Rect::new(0, 0, 10, 5), MouseCursor::Pointer);
+        cursors.set_region(Rect::new(8, 0, 1, 20), MouseCursor::ColResize);
+        assert_eq!(cursors.cursor_at(2, 2), MouseCursor::Pointer);
+        assert_eq!(cursors.cursor_at(8, 2), MouseCursor::ColResize);
+        assert_eq!(cursors.cursor_at(20, 2), MouseCursor::Text);
+        assert_eq!(cursors.cursor_at(10, 4), MouseCursor::Text);
+
+        // handles share their regions
+        cursors.clone().clear();
+        assert_eq!(cursors.cursor_at(2, 2), MouseCursor::Default);
+    }
+}
@@ -0,0 +1,626 @@
+//! Touch input handling.
+//!
+//! Touch input arrives as pointer events with a `pointerType` of `"touch"`.
+//! [`TouchMouseTracker`] maps them to the same [`MouseEventKind`]s a mouse
+//! would produce, so that applications work on touch screens unchanged:
+//!
+//! - A tap is a left click (and two quick taps a double click).
+//! - Dragging a finger presses the left button, drags, and releases it.
+//! - A long press is a right click, reported as soon as the finger has been
+//!   held down long enough.
+//!
+//! [`GestureTracker`] recognizes swipes and pinches on top of that.
+
+use web_sys::{wasm_bindgen::JsCast, Element};
+
+use crate::{
+    backend::event_callback::{
+        mouse_to_grid_coords, EventCallback, MouseConfig, SharedMouseConfig,
+    },
+    error::Error,
+    event::{GestureEvent, MouseButton, MouseEventKind, SwipeDirection},
+};
+
+/// The pointer event types used for touch input.
+pub(super) const TOUCH_EVENT_TYPES: &[&str] =
+    &["pointerdown", "pointermove", "pointerup", "pointercancel"];
+
+/// How far a finger may move, in CSS pixels, before a touch becomes a drag.
+const TAP_SLOP: f64 = 10.0;
+
+/// How long a finger must be held down, in milliseconds, for a right click.
+pub(super) const LONG_PRESS_DURATION: f64 = 500.0;
+
+/// The maximum time between two taps, in milliseconds, for a double click.
+const DOUBLE_TAP_INTERVAL: f64 = 300.0;
+
+/// How long after a touch, in milliseconds, mouse events emulated by the
+/// browser are ignored.
+const EMULATED_MOUSE_EVENT_WINDOW: f64 = 1000.0;
+
+/// The minimum distance, in CSS pixels, a finger must travel for a swipe.
+const SWIPE_MIN_DISTANCE: f64 = 50.0;
+
+/// The maximum duration of a swipe, in milliseconds.
+const SWIPE_MAX_DURATION: f64 = 500.0;
+
+/// The phase of a touch, derived from the pointer event type.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(super) enum TouchPhase {
+    /// A finger touched the screen.
+    Down,
+    /// A finger moved.
+    Move,
+    /// A finger was lifted.
+    Up,
+    /// The browser took over the touch, e.g. for scrolling.
+    Cancel,
+}
+
+impl TouchPhase {
+    /// Returns the phase for a pointer event type.
+    pub fn from_event_type(event_type: &str) -> Option<Self> {
+        match event_type {
+            "pointerdown" => Some(Self::Down),
+            "pointermove" => Some(Self::Move),
+            "pointerup" => Some(Self::Up),
+            "pointercancel" => Some(Self::Cancel),
+            _ => None,
+        }
+    }
+}
+
+/// A single touch pointer event.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct TouchInput {
+    /// The phase of the touch.
+    pub phase: TouchPhase,
+    /// Identifies the finger across events.
+    pub pointer_id: i32,
+    /// The position in CSS pixels.
+    pub x: f64,
+    /// The position in CSS pixels.
+    pub y: f64,
+    /// The column in the terminal grid.
+    pub col: u16,
+    /// The row in the terminal grid.
+    pub row: u16,
+    /// The event timestamp in milliseconds.
+    pub time: f64,
+}
+
+impl TouchInput {
+    /// Creates a [`TouchInput`] from a pointer event, if it is a touch.
+    pub fn from_pointer_event(
+        event: &web_sys::PointerEvent,
+        element: &Element,
+        config: &MouseConfig,
+    ) -> Option<Self> {
+        if event.pointer_type() != "touch" {
+            return None;
+        }
+        let phase = TouchPhase::from_event_type(&event.type_())?;
+        let (col, row) = mouse_to_grid_coords(event, element, config);
+        Some(Self {
+            phase,
+            pointer_id: event.pointer_id(),
+            x: event.client_x() as f64,
+            y: event.client_y() as f64,
+            col,
+            row,
+            time: event.time_stamp(),
+        })
+    }
+
+    /// Returns the distance to another input in CSS pixels.
+    fn distance(&self, other: &TouchInput) -> f64 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+/// The finger currently mapped to the mouse.
+#[derive(Debug, Clone, Copy)]
+struct ActiveTouch {
+    /// Where the finger touched the screen.
+    start: TouchInput,
+    /// Whether the finger moved far enough to be a drag.
+    dragging: bool,
+    /// Whether the finger was held long enough to be reported as a right
+    /// click.
+    long_pressed: bool,
+}
+
+/// Maps touch input to mouse events.
+///
+/// Only the first finger is mapped; further fingers are ignored until it is
+/// lifted.
+#[derive(Debug, Default)]
+pub(super) struct TouchMouseTracker {
+    /// The finger currently mapped to the mouse.
+    active: Option<ActiveTouch>,
+    /// The last tap, for detecting double taps.
+    last_tap: Option<TouchInput>,
+    /// The time of the last touch event.
+    last_touch_time: Option<f64>,
+}
+
+impl TouchMouseTracker {
+    /// Handles a touch and returns the mouse events it maps to, along with
+    /// their grid positions.
+    pub fn handle(&mut self, input: TouchInput) -> Vec<(MouseEventKind, u16, u16)> {
+        self.last_touch_time = Some(input.time);
+        if input.phase == TouchPhase::Down {
+            if self.active.is_none() {
+                self.active = Some(ActiveTouch {
+                    start: input,
+                    dragging: false,
+                    long_pressed: false,
+                });
+            }
+            return Vec::new();
+        }
+
+        let Some(active) = self
+            .active
+            .as_mut()
+            .filter(|active| active.start.pointer_id == input.pointer_id)
+        else {
+            return Vec::new();
+        };
+        let start = active.start;
+        let left = MouseButton::Left;
+
+        match input.phase {
+            TouchPhase::Down => Vec::new(),
+            TouchPhase::Move | TouchPhase::Up | TouchPhase::Cancel if active.long_pressed => {
+                // The right click was already reported
+                if input.phase != TouchPhase::Move {
+                    self.active = None;
+                }
+                Vec::new()
+            }
+            TouchPhase::Move => {
+                let mut events = Vec::new();
+                if !active.dragging && start.distance(&input) > TAP_SLOP {
+                    active.dragging = true;
+                    events.push((MouseEventKind::ButtonDown(left), start.col, start.row));
+                }
+                if active.dragging {
+                    events.push((MouseEventKind::Drag(left), input.col, input.row));
+                }
+                events
+            }
+            TouchPhase::Up if active.dragging => {
+                self.active = None;
+                vec![(MouseEventKind::ButtonUp(left), input.col, input.row)]
+            }
+            TouchPhase::Up if input.time - start.time >= LONG_PRESS_DURATION => {
+                self.active = None;
+                self.last_tap = None;
+                let right = MouseButton::Right;
+                vec![
+                    (MouseEventKind::ButtonDown(right), start.col, start.row),
+                    (MouseEventKind::ButtonUp(right), start.col, start.row),
+                ]
+            }
+            TouchPhase::Up => {
+                self.active = None;
+                let mut events = vec![
+                    (MouseEventKind::ButtonDown(left), start.col, start.row),
+                    (MouseEventKind::ButtonUp(left), start.col, start.row),
+                    (MouseEventKind::SingleClick(left), start.col, start.row),
+                ];
+                let is_double_tap = self.last_tap.is_some_and(|last| {
+                    start.time - last.time <= DOUBLE_TAP_INTERVAL
+                        && last.distance(&start) <= TAP_SLOP
+                });
+                if is_double_tap {
+                    self.last_tap = None;
+                    events.push((MouseEventKind::DoubleClick(left), start.col, start.row));
+                } else {
+                    self.last_tap = Some(input);
+                }
+                events
+            }
+            TouchPhase::Cancel => {
+                let dragging = active.dragging;
+                self.active = None;
+                if dragging {
+                    vec![(MouseEventKind::ButtonUp(left), input.col, input.row)]
+                } else {
+                    Vec::new()
+                }
+            }
+        }
+    }
+
+    /// Reports a long press as a right click, once the finger that touched
+    /// the screen at `start_time` has been held down for
+    /// [`LONG_PRESS_DURATION`].
+    ///
+    /// Returns nothing if the finger was lifted or started a drag since.
+    pub fn long_press(
+        &mut self,
+        pointer_id: i32,
+        start_time: f64,
+    ) -> Vec<(MouseEventKind, u16, u16)> {
+        let Some(active) = self.active.as_mut().filter(|active| {
+            active.start.pointer_id == pointer_id
+                && active.start.time == start_time
+                && !active.dragging
+                && !active.long_pressed
+        }) else {
+            return Vec::new();
+        };
+        active.long_pressed = true;
+        self.last_tap = None;
+        let (right, start) = (MouseButton::Right, active.start);
+        vec![
+            (MouseEventKind::ButtonDown(right), start.col, start.row),
+            (MouseEventKind::ButtonUp(right), start.col, start.row),
+        ]
+    }
+
+    /// Returns whether a mouse event at `time` was likely emulated by the
+    /// browser for a touch, and should be ignored.
+    pub fn is_emulated_mouse_event(&self, time: f64) -> bool {
+        self.active.is_some()
+            || self
+                .last_touch_time
+                .is_some_and(|last| time - last < EMULATED_MOUSE_EVENT_WINDOW)
+    }
+}
+
+/// A finger tracked for gesture recognition.
+#[derive(Debug, Clone, Copy)]
+struct TrackedTouch {
+    /// Where the finger touched the screen.
+    start: TouchInput,
+    /// Where the finger is now.
+    current: TouchInput,
+}
+
+/// Recognizes swipe and pinch gestures from touch input.
+#[derive(Debug, Default)]
+pub(super) struct GestureTracker {
+    /// The fingers currently on the screen.
+    touches: Vec<TrackedTouch>,
+    /// The distance between the first two fingers when the pinch started.
+    pinch_start: Option<f64>,
+    /// Whether more than one finger was used, which rules out a swipe.
+    multi_touch: bool,
+}
+
+impl GestureTracker {
+    /// Handles a touch and returns the gesture it completes, if any.
+    pub fn handle(&mut self, input: TouchInput) -> Option<GestureEvent> {
+        let index = self
+            .touches
+            .iter()
+            .position(|touch| touch.start.pointer_id == input.pointer_id);
+
+        match (input.phase, index) {
+            (TouchPhase::Down, None) => {
+                self.touches.push(TrackedTouch {
+                    start: input,
+                    current: input,
+                });
+                if self.touches.len() == 2 {
+                    self.multi_touch = true;
+                    self.pinch_start = Some(self.touches[0].current.distance(&input));
+                }
+                None
+            }
+            (TouchPhase::Move, Some(index)) => {
+                self.touches[index].current = input;
+                self.pinch()
+            }
+            (TouchPhase::Up | TouchPhase::Cancel, Some(index)) => {
+                let touch = self.touches.remove(index);
+                if index < 2 {
+                    self.pinch_start = None;
+                }
+                let mut gesture = None;
+                if self.touches.is_empty() {
+                    if input.phase == TouchPhase::Up && !self.multi_touch {
+                        gesture = swipe(&touch.start, &input);
+                    }
+                    self.multi_touch = false;
+                }
+                gesture
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the pinch for the current positions of the first two fingers.
+    fn pinch(&self) -> Option<GestureEvent> {
+        let start = self.pinch_start.filter(|start| *start > 0.0)?;
+        let [first, second, ..] = self.touches.as_slice() else {
+            return None;
+        };
+        let (first, second) = (first.current, second.current);
+        Some(GestureEvent::Pinch {
+            scale: first.distance(&second) / start,
+            col: ((first.col as u32 + second.col as u32) / 2) as u16,
+            row: ((first.row as u32 + second.row as u32) / 2) as u16,
+        })
+    }
+}
+
+/// Returns the swipe from `start` to `end`, if the finger travelled far and
+/// fast enough.
+fn swipe(start: &TouchInput, end: &TouchInput) -> Option<GestureEvent> {
+    let (dx, dy) = (end.x - start.x, end.y - start.y);
+    if end.time - start.time > SWIPE_MAX_DURATION || dx.abs().max(dy.abs()) < SWIPE_MIN_DISTANCE {
+        return None;
+    }
+    let direction = match (dx.abs() >= dy.abs(), dx < 0.0, dy < 0.0) {
+        (true, true, _) => SwipeDirection::Left,
+        (true, false, _) => SwipeDirection::Right,
+        (false, _, true) => SwipeDirection::Up,
+        (false, _, false) => SwipeDirection::Down,
+    };
+    Some(GestureEvent::Swipe {
+        direction,
+        col: start.col,
+        row: start.row,
+    })
+}
+
+/// Stops the browser from panning, zooming and showing callouts for touches
+/// on the element, so that they are delivered as pointer events instead.
+pub(super) fn disable_touch_actions(element: &Element) {
+    if let Some(element) = element.dyn_ref::<web_sys::HtmlElement>() {
+        let style = element.style();
+        let _ = style.set_property("touch-action", "none");
+        let _ = style.set_property("-webkit-touch-callout", "none");
+    }
+}
+
+/// Creates an [`EventCallback`] that reports touch gestures on the element.
+///
+/// The config is read for every event, so that updates after a resize are
+/// picked up.
+pub(super) fn create_gesture_callback<F>(
+    element: Element,
+    config: SharedMouseConfig,
+    mut callback: F,
+) -> Result<EventCallback<web_sys::PointerEvent>, Error>
+where
+    F: FnMut(GestureEvent) + 'static,
+{
+    disable_touch_actions(&element);
+    let mut tracker = GestureTracker::default();
+    let target = element.clone();
+    EventCallback::new(
+        target,
+        TOUCH_EVENT_TYPES,
+        move |event: web_sys::PointerEvent| {
+            let config = config.borrow().clone();
+            let Some(input) = TouchInput::from_pointer_event(&event, &element, &config) else {
+                return;
+            };
+            if let Some(gesture) = tracker.handle(input) {
+                callback(gesture);
+            }
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn touch(phase: TouchPhase, pointer_id: i32, x: f64, y: f64, time: f64) -> TouchInput {
+        TouchInput {
+            phase,
+            pointer_id,
+            x,
+            y,
+            col: (x / 10.0) as u16,
+            row: (y / 20.0) as u16,
+            time,
+        }
+    }
+
+    #[test]
+    fn test_tap_is_left_click() {
+        let mut tracker = TouchMouseTracker::default();
+        assert!(tracker
+            .handle(touch(TouchPhase::Down, 1, 25.0, 45.0, 0.0))
+            .is_empty());
+        // small movements don't start a drag
+        assert!(tracker
+            .handle(touch(TouchPhase::Move, 1, 28.0, 47.0, 50.0))
+            .is_empty());
+        assert_eq!(
+            tracker.handle(touch(TouchPhase::Up, 1, 28.0, 47.0, 100.0)),
+            vec![
+                (MouseEventKind::ButtonDown(MouseButton::Left), 2, 2),
+                (MouseEventKind::ButtonUp(MouseButton::Left), 2, 2),
+                (MouseEventKind::SingleClick(MouseButton::Left), 2, 2),
+            ]
+        );
+        assert!(tracker.is_emulated_mouse_event(500.0));
+        assert!(!tracker.is_emulated_mouse_event(2000.0));
+    }
+
+    #[test]
+    fn test_double_tap() {
+        let mut tracker = TouchMouseTracker::default();
+        tracker.handle(touch(TouchPhase::Down, 1, 25.0, 45.0, 0.0));
+        tracker.handle(touch(TouchPhase::Up, 1, 25.0, 45.0, 50.0));
+        tracker.handle(touch(TouchPhase::Down, 2, 27.0, 45.0, 200.0));
+        let events = tracker.handle(touch(TouchPhase::Up, 2, 27.0, 45.0, 250.0));
+        assert_eq!(
+            events.last(),
+            Some(&(MouseEventKind::DoubleClick(MouseButton::Left), 2, 2))
+        );
+
+        // a third tap starts over
+        tracker.handle(touch(TouchPhase::Down, 3, 27.0, 45.0, 400.0));
+        let events = tracker.handle(touch(TouchPhase::Up, 3, 27.0, 45.0, 450.0));
+        assert_eq!(events.len(), 3);
+    }
+
+    #[test]
+    fn test_long_press_is_right_click() {
+        let mut tracker = TouchMouseTracker::default();
+        tracker.handle(touch(TouchPhase::Down, 1, 25.0, 45.0, 0.0));
+        assert_eq!(
+            tracker.handle(touch(TouchPhase::Up, 1, 25.0, 45.0, 600.0)),
+            vec![
+                (MouseEventKind::ButtonDown(MouseButton::Right), 2, 2),
+                (MouseEventKind::ButtonUp(MouseButton::Right), 2, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_long_press_timer() {
+        let mut tracker = TouchMouseTracker::default();
+        tracker.handle(touch(TouchPhase::Down, 1, 25.0, 45.0, 0.0));
+        assert_eq!(
+            tracker.long_press(1, 0.0),
+            vec![
+                (MouseEventKind::ButtonDown(MouseButton::Right), 2, 2),
+                (MouseEventKind::ButtonUp(MouseButton::Right), 2, 2),
+            ]
+        );
+        // it is reported once, and nothing else until the finger is lifted
+        assert!(tracker.long_press(1, 0.0).is_empty());
+        assert!(tracker
+            .handle(touch(TouchPhase::Move, 1, 60.0, 45.0, 600.0))
+            .is_empty());
+        assert!(tracker
+            .handle(touch(TouchPhase::Up, 1, 60.0, 45.0, 700.0))
+            .is_empty());
+
+        // not after a drag, or for an earlier touch
+        tracker.handle(touch(TouchPhase::Down, 2, 25.0, 45.0, 1000.0));
+        tracker.handle(touch(TouchPhase::Move, 2, 55.0, 45.0, 1100.0));
+        assert!(tracker.long_press(2, 1000.0).is_empty());
+        tracker.handle(touch(TouchPhase::Up, 2, 55.0, 45.0, 1200.0));
+        tracker.handle(touch(TouchPhase::Down, 1, 25.0, 45.0, 2000.0));
+        assert!(tracker.long_press(1, 0.0).is_empty());
+    }
+
+    #[test]
+    fn test_drag() {
+        let mut tracker = TouchMouseTracker::default();
+        tracker.handle(touch(TouchPhase::Down, 1, 25.0, 45.0, 0.0));
+        assert_eq!(
+            tracker.handle(touch(TouchPhase::Move, 1, 55.0, 45.0, 50.0)),
+            vec![
+                (MouseEventKind::ButtonDown(MouseButton::Left), 2, 2),
+                (MouseEventKind::Drag(MouseButton::Left), 5, 2),
+            ]
+        );
+        // other fingers are ignored
+        tracker.handle(touch(TouchPhase::Down, 2, 0.0, 0.0, 60.0));
+        assert!(tracker
+            .handle(touch(TouchPhase::Move, 2, 90.0, 0.0, 70.0))
+            .is_empty());
+        assert_eq!(
+            tracker.handle(touch(TouchPhase::Move, 1, 75.0, 65.0, 80.0)),
+            vec![(MouseEventKind::Drag(MouseButton::Left), 7, 3)]
+        );
+        assert_eq!(
+            tracker.handle(touch(TouchPhase::Up, 1, 75.0, 65.0, 900.0)),
+            vec![(MouseEventKind::ButtonUp(MouseButton::Left), 7, 3)]
+        );
+    }
+
+    #[test]
+    fn test_cancel() {
+        let mut tracker = TouchMouseTracker::default();
+        tracker.handle(touch(TouchPhase::Down, 1, 25.0, 45.0, 0.0));
+        assert!(tracker
+            .handle(touch(TouchPhase::Cancel, 1, 25.0, 45.0, 10.0))
+            .is_empty());
+
+        tracker.handle(touch(TouchPhase::Down, 1, 25.0, 45.0, 100.0));
+        tracker.handle(touch(TouchPhase::Move, 1, 55.0, 45.0, 150.0));
+        assert_eq!(
+            tracker.handle(touch(TouchPhase::Cancel, 1, 55.0, 45.0, 200.0)),
+            vec![(MouseEventKind::ButtonUp(MouseButton::Left), 5, 2)]
+        );
+    }
+
+    #[test]
+    fn test_swipe() {
+        let cases = [
+            (100.0, 0.0, SwipeDirection::Right),
+            (-100.0, 10.0, SwipeDirection::Left),
+            (10.0, -100.0, SwipeDirection::Up),
+            (0.0, 100.0, SwipeDirection::Down),
+        ];
+        for (dx, dy, direction) in cases {
+            let mut tracker = GestureTracker::default();
+            assert_eq!(
+                tracker.handle(touch(TouchPhase::Down, 1, 200.0, 200.0, 0.0)),
+                None
+            );
+            tracker.handle(touch(TouchPhase::Move, 1, 200.0 + dx, 200.0 + dy, 100.0));
+            assert_eq!(
+                tracker.handle(touch(TouchPhase::Up, 1, 200.0 + dx, 200.0 + dy, 200.0)),
+                Some(GestureEvent::Swipe {
+                    direction,
+                    col: 20,
+                    row: 10
+                })
+            );
+        }
+
+        // too short or too slow
+        let mut tracker = GestureTracker::default();
+        tracker.handle(touch(TouchPhase::Down, 1, 200.0, 200.0, 0.0));
+        assert_eq!(
+            tracker.handle(touch(TouchPhase::Up, 1, 230.0, 200.0, 100.0)),
+            None
+        );
+        tracker.handle(touch(TouchPhase::Down, 1, 200.0, 200.0, 0.0));
+        assert_eq!(
+            tracker.handle(touch(TouchPhase::Up, 1, 300.0, 200.0, 900.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_pinch() {
+        let mut tracker = GestureTracker::default();
+        tracker.handle(touch(TouchPhase::Down, 1, 100.0, 100.0, 0.0));
+        tracker.handle(touch(TouchPhase::Down, 2, 200.0, 100.0, 10.0));
+        assert_eq!(
+            tracker.handle(touch(TouchPhase::Move, 2, 300.0, 100.0, 50.0)),
+            Some(GestureEvent::Pinch {
+                scale: 2.0,
+                col: 20,
+                row: 5
+            })
+        );
+        assert_eq!(
+            tracker.handle(touch(TouchPhase::Move, 1, 250.0, 100.0, 60.0)),
+            Some(GestureEvent::Pinch {
+                scale: 0.5,
+                col: 27,
+                row: 5
+            })
+        );
+
+        // lifting the fingers doesn't report a swipe
+        assert_eq!(
+            tracker.handle(touch(TouchPhase::Up, 2, 300.0, 100.0, 70.0)),
+            None
+        );
+        assert_eq!(
+            tracker.handle(touch(TouchPhase::Move, 1, 400.0, 100.0, 80.0)),
+            None
+        );
+        assert_eq!(
+            tracker.handle(touch(TouchPhase::Up, 1, 400.0, 100.0, 90.0)),
+            None
+        );
+    }
+}
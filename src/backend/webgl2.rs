@@ -1,11 +1,20 @@
 use crate::{
     backend::{
         color::to_rgb,
-        event_callback::{EventCallback, KEY_EVENT_TYPES},
+        event_callback::{
+            blur_element, create_focus_callback, create_key_callback, create_mouse_callback,
+            create_paste_callback, element_has_focus, focus_element, EventCallback, MouseConfig,
+            ResizeCallback, SharedMouseConfig, MOUSE_EVENT_TYPES,
+        },
+        gamepad::{GamepadCallback, DEFAULT_GAMEPAD_DEAD_ZONE},
+        ime::{ImeInput, TerminalElements},
+        key_capture::KeyCapture,
+        mouse_cursor::{MouseCursor, MouseCursorCallback, MouseCursors},
+        touch::create_gesture_callback,
         utils::*,
     },
     error::Error,
-    event::{KeyEvent, MouseEvent},
+    event::{FocusEvent, GamepadEvent, GestureEvent, ImeEvent, KeyEvent, MouseEvent, ResizeEvent},
     render::WebEventHandler,
     CellSized, CursorShape,
 };
@@ -93,6 +102,22 @@ pub struct WebGl2BackendOptions {
     console_debug_api: bool,
     /// Disable automatic canvas CSS sizing (let external CSS control dimensions).
     disable_auto_css_resize: bool,
+    /// Prevent the page from scrolling on mouse wheel events.
+    prevent_default_scroll: bool,
+    /// Prevent the page from panning and zooming on touches.
+    prevent_default_touch: bool,
+    /// Report key release events.
+    report_key_release_events: bool,
+    /// Keys whose default browser action is prevented.
+    key_capture: KeyCapture,
+    /// Listen for key events on the document instead of the terminal.
+    document_key_events: bool,
+    /// Report touch gestures to the unified event handler.
+    gesture_events: bool,
+    /// The dead zone of gamepad axes.
+    gamepad_dead_zone: Option<f64>,
+    /// Focus the terminal when the backend is created.
+    autofocus: bool,
 }
 
 impl WebGl2BackendOptions {
@@ -249,6 +274,86 @@ impl WebGl2BackendOptions {
         self.disable_auto_css_resize = true;
         self
     }
+
+    /// Sets whether mouse wheel events over the canvas should be prevented
+    /// from scrolling the page.
+    ///
+    /// Scroll events are still delivered to [`WebEventHandler::on_mouse_event`].
+    pub fn prevent_default_scroll(mut self, prevent: bool) -> Self {
+        self.prevent_default_scroll = prevent;
+        self
+    }
+
+    /// Sets whether touches on the canvas should be prevented from panning
+    /// and zooming the page.
+    ///
+    /// This lets dragging a finger over the canvas be reported as a mouse
+    /// drag to [`WebEventHandler::on_mouse_event`], instead of the browser
+    /// taking over the touch to scroll the page.
+    pub fn prevent_default_touch(mut self, prevent: bool) -> Self {
+        self.prevent_default_touch = prevent;
+        self
+    }
+
+    /// Sets whether key releases are reported as
+    /// [`KeyEventKind::Release`](crate::event::KeyEventKind::Release)
+    /// events in addition to presses and repeats.
+    ///
+    /// This is disabled by default so that handlers which don't check
+    /// [`KeyEvent::kind`](crate::event::KeyEvent::kind) aren't called twice
+    /// for every key stroke.
+    pub fn report_key_release_events(mut self, report: bool) -> Self {
+        self.report_key_release_events = report;
+        self
+    }
+
+    /// Sets which keys have their default browser action prevented, e.g. so
+    /// that Tab doesn't move the focus away from the terminal.
+    ///
+    /// No keys are captured by default. See [`KeyCapture`] for details.
+    pub fn key_capture(mut self, capture: KeyCapture) -> Self {
+        self.key_capture = capture;
+        self
+    }
+
+    /// Sets whether key events are listened for on the whole document rather
+    /// than on the terminal, so that they are received without the terminal
+    /// having focus.
+    ///
+    /// This is useful for applications that take up the whole page.
+    pub fn document_key_events(mut self, enabled: bool) -> Self {
+        self.document_key_events = enabled;
+        self
+    }
+
+    /// Sets whether touch gestures are reported to
+    /// [`WebEventHandler::on_event`].
+    ///
+    /// This is disabled by default, as recognizing gestures stops the browser
+    /// from panning and zooming the page on touches over the terminal.
+    /// Handlers set with [`WebEventHandler::on_gesture_event`] always receive
+    /// gestures.
+    pub fn gesture_events(mut self, enabled: bool) -> Self {
+        self.gesture_events = enabled;
+        self
+    }
+
+    /// Sets the dead zone of gamepad axes, between `0.0` and `1.0`.
+    ///
+    /// Axis values closer to the center than this are reported as `0.0`, so
+    /// that sticks that don't return exactly to the center aren't reported
+    /// as moved. Defaults to `0.1`.
+    pub fn gamepad_dead_zone(mut self, dead_zone: f64) -> Self {
+        self.gamepad_dead_zone = Some(dead_zone);
+        self
+    }
+
+    /// Sets whether the terminal should receive keyboard focus when the backend is created, so
+    /// that key events are delivered without clicking it first.
+    pub fn autofocus(mut self, autofocus: bool) -> Self {
+        self.autofocus = autofocus;
+        self
+    }
 }
 
 /// WebGl2 backend for high-performance terminal rendering.
@@ -328,9 +433,28 @@ pub struct WebGl2Backend {
     /// Shared state for deferred hyperlink processing in [`WebGl2Backend::flush`].
     hyperlink_state: Option<Rc<std::cell::Cell<PendingHyperlinkEvent>>>,
     /// User-provided mouse event handler.
-    _user_mouse_handler: Option<TerminalMouseHandler>,
+    _user_mouse_handler: Option<EventCallback<web_sys::MouseEvent>>,
+    /// User-provided gesture event handler.
+    _user_gesture_handler: Option<EventCallback<web_sys::PointerEvent>>,
     /// User-provided key event handler.
     _user_key_handler: Option<EventCallback<web_sys::KeyboardEvent>>,
+    /// User-provided paste event handler.
+    _user_paste_handler: Option<EventCallback<web_sys::ClipboardEvent>>,
+    /// User-provided focus event handler.
+    _user_focus_handler: Option<EventCallback<web_sys::FocusEvent>>,
+    /// User-provided resize handler.
+    resize_callback: Option<ResizeCallback>,
+    /// The canvas element and its IME input.
+    elements: TerminalElements,
+    /// IME composition input.
+    ime_input: Option<ImeInput>,
+    /// Gamepad event callback handler.
+    gamepad_callback: Option<GamepadCallback>,
+    /// Mouse cursor regions.
+    mouse_cursor_callback: Option<MouseCursorCallback>,
+    /// The coordinate translation shared with the mouse and gesture event
+    /// handlers.
+    shared_mouse_config: SharedMouseConfig,
 }
 
 impl WebGl2Backend {
@@ -360,6 +484,9 @@ impl WebGl2Backend {
         let parent = get_element_by_id_or_body(options.grid_id.as_ref())?;
 
         let beamterm = Self::init_beamterm(&mut options, &parent)?;
+        if options.autofocus {
+            focus_element(beamterm.canvas())?;
+        }
 
         // Extract hyperlink callback from options
         let hyperlink_callback = options.hyperlink_callback.take();
@@ -373,7 +500,8 @@ impl WebGl2Backend {
             (None, None)
         };
 
-        Ok(Self {
+        let elements = TerminalElements::new(beamterm.canvas().clone().into());
+        let backend = Self {
             beamterm,
             cursor_position: None,
             options,
@@ -383,8 +511,39 @@ impl WebGl2Backend {
             hyperlink_callback,
             hyperlink_state,
             _user_mouse_handler: None,
+            _user_gesture_handler: None,
             _user_key_handler: None,
-        })
+            _user_paste_handler: None,
+            _user_focus_handler: None,
+            resize_callback: None,
+            elements,
+            ime_input: None,
+            gamepad_callback: None,
+            mouse_cursor_callback: None,
+            shared_mouse_config: SharedMouseConfig::default(),
+        };
+        backend.update_mouse_config();
+        Ok(backend)
+    }
+
+    /// Returns a handle for setting the mouse cursor over regions of the
+    /// terminal.
+    ///
+    /// The pointer cursor over hyperlinks takes precedence over the regions.
+    /// See [`MouseCursors`] for details.
+    pub fn mouse_cursors(&mut self) -> Result<MouseCursors, Error> {
+        if let Some(callback) = &self.mouse_cursor_callback {
+            return Ok(callback.cursors());
+        }
+        let callback = MouseCursorCallback::new(
+            self.beamterm.canvas().clone().into(),
+            self.mouse_config(),
+            MouseCursors::default(),
+        )?;
+        callback.set_override(self.cursor_over_hyperlink.then_some(MouseCursor::Pointer));
+        let cursors = callback.cursors();
+        self.mouse_cursor_callback = Some(callback);
+        Ok(cursors)
     }
 
     /// Returns the options objects used to create this backend.
@@ -415,7 +574,7 @@ impl WebGl2Backend {
         self.beamterm.resize(width, height)?;
 
         // Reset hyperlink cursor state when canvas is resized
-        self.cursor_over_hyperlink = false;
+        self.set_cursor_over_hyperlink(false);
 
         Ok(())
     }
@@ -441,7 +600,7 @@ impl WebGl2Backend {
     /// cell size.
     pub fn set_size(&mut self, width: u32, height: u32) -> Result<(), Error> {
         self.beamterm.resize(width as i32, height as i32)?;
-        self.cursor_over_hyperlink = false;
+        self.set_cursor_over_hyperlink(false);
         Ok(())
     }
 
@@ -457,6 +616,8 @@ impl WebGl2Backend {
         if display_width != stored_width || display_height != stored_height {
             self.resize_canvas()?;
         }
+        // The grid may also have been resized via `set_size`
+        self.update_mouse_config();
 
         Ok(())
     }
@@ -592,6 +753,43 @@ impl WebGl2Backend {
         Ok(mouse_handler)
     }
 
+    /// Sets whether the pointer is over a hyperlink, showing the pointer
+    /// cursor over any mouse cursor regions.
+    fn set_cursor_over_hyperlink(&mut self, is_over: bool) {
+        self.cursor_over_hyperlink = is_over;
+        if let Some(callback) = &self.mouse_cursor_callback {
+            callback.set_override(is_over.then_some(MouseCursor::Pointer));
+        }
+    }
+
+    /// Returns the element the canvas, and its IME input, are placed in.
+    fn container(&self) -> Result<Element, Error> {
+        self.beamterm
+            .canvas()
+            .parent_element()
+            .ok_or(Error::UnableToRetrieveComponent("canvas parent"))
+    }
+
+    /// Returns the coordinate translation for mouse events.
+    ///
+    /// Mouse events are translated using the CSS cell size of the grid,
+    /// rather than via beamterm, so that the same events are reported as
+    /// by the other backends.
+    fn mouse_config(&self) -> MouseConfig {
+        let ts = self.beamterm.terminal_size();
+        let (cell_width, cell_height) = self.cell_size_css_px();
+        MouseConfig::new(ts.cols, ts.rows)
+            .with_cell_dimensions(cell_width as f64, cell_height as f64)
+            .with_prevent_default_scroll(self.options.prevent_default_scroll)
+            .with_prevent_default_touch(self.options.prevent_default_touch)
+    }
+
+    /// Updates the coordinate translation used by the mouse and gesture event
+    /// handlers to the current grid size and cell metrics.
+    fn update_mouse_config(&self) {
+        *self.shared_mouse_config.borrow_mut() = self.mouse_config();
+    }
+
     /// Processes pending hyperlink events using [`Beamterm::find_url_at`].
     ///
     /// Called during [`WebGl2Backend::flush`] where `self.beamterm` is accessible.
@@ -622,8 +820,10 @@ impl WebGl2Backend {
                 .find_url_at(CursorPosition::new(col, row))
                 .is_some();
             if self.cursor_over_hyperlink != is_over {
-                self.cursor_over_hyperlink = is_over;
-                Self::update_canvas_cursor_style(&self.beamterm.canvas(), is_over);
+                self.set_cursor_over_hyperlink(is_over);
+                if self.mouse_cursor_callback.is_none() {
+                    Self::update_canvas_cursor_style(&self.beamterm.canvas(), is_over);
+                }
             }
         }
 
@@ -712,6 +912,15 @@ impl Backend for WebGl2Backend {
     fn flush(&mut self) -> IoResult<()> {
         self.process_hyperlink_events();
         self.check_canvas_resize()?;
+        if let Some(callback) = &self.mouse_cursor_callback {
+            callback.refresh(self.mouse_config());
+        }
+        if self.resize_callback.is_some() {
+            let size = self.window_size()?.into();
+            if let Some(resize_callback) = &mut self.resize_callback {
+                resize_callback.update(size);
+            }
+        }
 
         self.measure_begin(WEBGL_RENDER_MARK);
 
@@ -768,7 +977,12 @@ impl Backend for WebGl2Backend {
     }
 
     fn set_cursor_position<P: Into<Position>>(&mut self, position: P) -> IoResult<()> {
-        self.cursor_position = Some(position.into());
+        let position = position.into();
+        self.cursor_position = Some(position);
+        if let Some(ime_input) = &self.ime_input {
+            let (width, height) = self.cell_size_css_px();
+            ime_input.set_cursor_position(position, (width.into(), height.into()));
+        }
         Ok(())
     }
 
@@ -859,28 +1073,13 @@ impl std::fmt::Debug for HyperlinkCallback {
 
 /// Event handling for [`WebGl2Backend`].
 ///
-/// This implementation delegates mouse events to beamterm's [`TerminalMouseHandler`],
-/// which provides native grid coordinate translation.
-///
-/// | Supported | Event Type                      |
-/// | --------- | ------------------------------- |
-/// | ✓         | [`MouseEventKind::Moved`]       |
-/// | ✓         | [`MouseEventKind::ButtonDown`]  |
-/// | ✓         | [`MouseEventKind::ButtonUp`]    |
-/// | ✓         | [`MouseEventKind::SingleClick`] |
-/// | ✗         | [`MouseEventKind::DoubleClick`] |
-/// | ✓         | [`MouseEventKind::Entered`]     |
-/// | ✓         | [`MouseEventKind::Exited`]      |
+/// Mouse events on the canvas are translated to grid coordinates using the
+/// CSS cell size of the terminal. All [`MouseEventKind`]s are reported, the
+/// same as for the other backends.
 ///
 /// Keyboard events are supported by making the canvas focusable with `tabindex="0"`.
 ///
-/// [`MouseEventKind::Moved`]: crate::event::MouseEventKind::Moved
-/// [`MouseEventKind::ButtonDown`]: crate::event::MouseEventKind::ButtonDown
-/// [`MouseEventKind::ButtonUp`]: crate::event::MouseEventKind::ButtonUp
-/// [`MouseEventKind::SingleClick`]: crate::event::MouseEventKind::SingleClick
-/// [`MouseEventKind::DoubleClick`]: crate::event::MouseEventKind::DoubleClick
-/// [`MouseEventKind::Entered`]: crate::event::MouseEventKind::Entered
-/// [`MouseEventKind::Exited`]: crate::event::MouseEventKind::Exited
+/// [`MouseEventKind`]: crate::event::MouseEventKind
 impl WebEventHandler for WebGl2Backend {
     fn on_mouse_event<F>(&mut self, callback: F) -> Result<(), Error>
     where
@@ -889,35 +1088,43 @@ impl WebEventHandler for WebGl2Backend {
         // Clear any existing handlers first
         self.clear_mouse_events();
 
-        let grid = self.beamterm.grid();
-        let canvas = self.beamterm.canvas();
+        let element: web_sys::Element = self.beamterm.canvas().clone().into();
+        self._user_mouse_handler = Some(create_mouse_callback(
+            element,
+            MOUSE_EVENT_TYPES,
+            self.shared_mouse_config.clone(),
+            callback,
+        )?);
 
-        // Wrap the callback in Rc<RefCell> for sharing
-        let callback = Rc::new(RefCell::new(callback));
-        let callback_clone = callback.clone();
+        Ok(())
+    }
 
-        // Create a TerminalMouseHandler that delegates to our callback
-        let mouse_handler = TerminalMouseHandler::new(
-            canvas,
-            grid,
-            move |event: TerminalMouseEvent, _grid: &beamterm_renderer::TerminalGrid| {
-                let mouse_event = MouseEvent::from(&event);
-                if let Ok(mut cb) = callback_clone.try_borrow_mut() {
-                    cb(mouse_event);
-                }
-            },
-        )?;
+    fn clear_mouse_events(&mut self) {
+        self._user_mouse_handler = None;
+    }
+
+    fn on_gesture_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(GestureEvent) + 'static,
+    {
+        // Clear any existing handlers first
+        self.clear_gesture_events();
 
-        self._user_mouse_handler = Some(mouse_handler);
+        let element: web_sys::Element = self.beamterm.canvas().clone().into();
+        self._user_gesture_handler = Some(create_gesture_callback(
+            element,
+            self.shared_mouse_config.clone(),
+            callback,
+        )?);
 
         Ok(())
     }
 
-    fn clear_mouse_events(&mut self) {
-        self._user_mouse_handler = None;
+    fn clear_gesture_events(&mut self) {
+        self._user_gesture_handler = None;
     }
 
-    fn on_key_event<F>(&mut self, mut callback: F) -> Result<(), Error>
+    fn on_key_event<F>(&mut self, callback: F) -> Result<(), Error>
     where
         F: FnMut(KeyEvent) + 'static,
     {
@@ -925,17 +1132,17 @@ impl WebEventHandler for WebGl2Backend {
         self.clear_key_events();
 
         let canvas = self.beamterm.canvas();
-        let element: web_sys::Element = canvas.clone().into();
 
         // Make the canvas focusable so it can receive key events
         canvas.set_attribute("tabindex", "0").map_err(Error::from)?;
 
-        self._user_key_handler = Some(EventCallback::new(
-            element,
-            KEY_EVENT_TYPES,
-            move |event: web_sys::KeyboardEvent| {
-                callback(event.into());
-            },
+        self._user_key_handler = Some(create_key_callback(
+            self.beamterm.canvas().clone().into(),
+            self.elements.clone(),
+            self.options.report_key_release_events,
+            self.options.key_capture.clone(),
+            self.options.document_key_events,
+            callback,
         )?);
 
         Ok(())
@@ -944,8 +1151,150 @@ impl WebEventHandler for WebGl2Backend {
     fn clear_key_events(&mut self) {
         self._user_key_handler = None;
     }
+
+    fn on_paste_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(String) + 'static,
+    {
+        // Clear any existing handlers first
+        self.clear_paste_events();
+
+        let canvas = self.beamterm.canvas();
+        let element: web_sys::Element = canvas.clone().into();
+
+        // Make the canvas focusable so it can receive paste events
+        canvas.set_attribute("tabindex", "0").map_err(Error::from)?;
+
+        self._user_paste_handler = Some(create_paste_callback(
+            element,
+            self.elements.clone(),
+            callback,
+        )?);
+
+        Ok(())
+    }
+
+    fn clear_paste_events(&mut self) {
+        self._user_paste_handler = None;
+    }
+
+    fn on_focus_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(FocusEvent) + 'static,
+    {
+        // Clear any existing handlers first
+        self.clear_focus_events();
+
+        self._user_focus_handler = Some(create_focus_callback(
+            self.container()?,
+            self.elements.clone(),
+            callback,
+        )?);
+
+        Ok(())
+    }
+
+    fn clear_focus_events(&mut self) {
+        self._user_focus_handler = None;
+    }
+
+    fn on_resize<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(ResizeEvent) + 'static,
+    {
+        let size = self.window_size().ok().map(Into::into);
+        self.resize_callback = Some(ResizeCallback::new(size, callback));
+        Ok(())
+    }
+
+    fn clear_resize_events(&mut self) {
+        self.resize_callback = None;
+    }
+
+    fn focus(&self) -> Result<(), Error> {
+        focus_element(self.beamterm.canvas())
+    }
+
+    fn blur(&self) -> Result<(), Error> {
+        blur_element(&self.elements)
+    }
+
+    fn has_focus(&self) -> bool {
+        element_has_focus(&self.elements)
+    }
+
+    fn on_ime_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(ImeEvent) + 'static,
+    {
+        // Clear any existing handlers first
+        self.clear_ime_events();
+
+        let canvas = self.beamterm.canvas();
+
+        // Make the canvas focusable so it can hand focus to the IME input
+        canvas.set_attribute("tabindex", "0").map_err(Error::from)?;
+
+        let (width, height) = self.cell_size_css_px();
+        self.ime_input = Some(ImeInput::new(
+            &self.elements,
+            &self.container()?,
+            (width.into(), height.into()),
+            0.0,
+            callback,
+        )?);
+
+        Ok(())
+    }
+
+    fn clear_ime_events(&mut self) {
+        self.ime_input = None;
+    }
+
+    fn on_gamepad_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(GamepadEvent) + 'static,
+    {
+        self.gamepad_callback = Some(GamepadCallback::new(
+            self.options
+                .gamepad_dead_zone
+                .unwrap_or(DEFAULT_GAMEPAD_DEAD_ZONE),
+            callback,
+        ));
+        Ok(())
+    }
+
+    fn clear_gamepad_events(&mut self) {
+        self.gamepad_callback = None;
+    }
+
+    fn poll_gamepads(&mut self) -> Option<bool> {
+        self.gamepad_callback.as_mut().map(GamepadCallback::poll)
+    }
+
+    fn ime_input_element(&self) -> Option<Element> {
+        self.ime_input.as_ref().map(ImeInput::textarea)
+    }
+
+    fn handles_document_key_events(&self) -> bool {
+        self.options.document_key_events
+    }
+
+    fn handles_gesture_events(&self) -> bool {
+        self.options.gesture_events
+    }
+
+    fn terminal_element(&self) -> Option<Element> {
+        Some(self.beamterm.canvas().clone().into())
+    }
 }
 
+/// Converts a mouse event reported by beamterm.
+///
+/// The conversion is lossy: beamterm has no equivalent of scroll, double
+/// click or drag events, and doesn't report the position within the cell.
+/// The backend itself doesn't use this conversion, the events passed to
+/// [`WebEventHandler::on_mouse_event`] are created from the browser events.
 impl From<&TerminalMouseEvent> for MouseEvent {
     fn from(event: &TerminalMouseEvent) -> Self {
         use crate::event::{MouseButton, MouseEventKind};
@@ -965,9 +1314,11 @@ impl From<&TerminalMouseEvent> for MouseEvent {
             kind,
             col: event.col,
             row: event.row,
+            offset: None,
             ctrl: event.ctrl_key(),
             alt: event.alt_key(),
             shift: event.shift_key(),
+            meta: event.meta_key(),
         }
     }
 }
@@ -977,6 +1328,9 @@ mod tests {
     use super::*;
     use beamterm_renderer::{FontStyle, GlyphEffect};
     use ratatui::style::Modifier;
+    use wasm_bindgen_test::*;
+
+    use crate::backend::event_callback::mouse_event_matrix;
 
     #[test]
     fn test_font_style() {
@@ -1000,4 +1354,11 @@ mod tests {
         .map(|(effect, modifier)| (effect as u16, into_glyph_bits(modifier)))
         .for_each(|(expected, actual)| assert_eq!(expected, actual));
     }
+
+    #[wasm_bindgen_test]
+    fn test_mouse_event_matrix() {
+        let mut backend = WebGl2Backend::new().expect("failed to create backend");
+        let target: Element = backend.beamterm.canvas().clone().into();
+        mouse_event_matrix::check(&mut backend, &target);
+    }
 }
@@ -43,6 +43,32 @@ pub enum Error {
     /// Failed to retrieve a HTML/js component, such as `Performance`.
     #[error("Failed to retrieve component: {0}")]
     UnableToRetrieveComponent(&'static str),
+
+    /// An event has no equivalent in another crate's event types.
+    ///
+    /// This error occurs when converting e.g. a [`MouseEventKind::Entered`]
+    /// to crossterm.
+    ///
+    /// [`MouseEventKind::Entered`]: crate::event::MouseEventKind::Entered
+    #[error("Unsupported event: {0}")]
+    UnsupportedEvent(String),
+
+    /// A key binding could not be parsed, or conflicts with another binding.
+    ///
+    /// This error occurs when binding keys with [`Keymap::bind`].
+    ///
+    /// [`Keymap::bind`]: crate::event::Keymap::bind
+    #[error("Invalid key binding: {0}")]
+    InvalidKeyBinding(String),
+
+    /// The backend doesn't support an operation.
+    ///
+    /// This error is returned by the default implementations of e.g.
+    /// [`WebEventHandler::focus`].
+    ///
+    /// [`WebEventHandler::focus`]: crate::WebEventHandler::focus
+    #[error("Unsupported operation: {0}")]
+    Unsupported(&'static str),
 }
 
 /// Convert [`wasm_bindgen::JsValue`] to [`Error`].
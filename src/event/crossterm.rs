@@ -0,0 +1,478 @@
+//! Conversions between ratzilla and [`crossterm`](::crossterm) events.
+//!
+//! Conversions to crossterm fail for events that crossterm can't represent,
+//! e.g. [`MouseEventKind::Entered`]. Conversions from crossterm fail for keys
+//! that browsers don't report, e.g. [`ct::MediaKeyCode::Reverse`].
+//!
+//! The meta key ([`KeyEvent::meta`]) corresponds to crossterm's
+//! [`ct::KeyModifiers::SUPER`].
+
+use ::crossterm::event as ct;
+
+use crate::{
+    error::Error,
+    event::{
+        Event, KeyCode, KeyEvent, KeyEventKind, MediaKeyCode, ModifierKeyCode, MouseButton,
+        MouseEvent, MouseEventKind,
+    },
+};
+
+/// Returns the crossterm modifiers for the given modifier key states.
+fn modifiers(ctrl: bool, alt: bool, shift: bool, meta: bool) -> ct::KeyModifiers {
+    let mut modifiers = ct::KeyModifiers::NONE;
+    modifiers.set(ct::KeyModifiers::CONTROL, ctrl);
+    modifiers.set(ct::KeyModifiers::ALT, alt);
+    modifiers.set(ct::KeyModifiers::SHIFT, shift);
+    modifiers.set(ct::KeyModifiers::SUPER, meta);
+    modifiers
+}
+
+/// Returns an [`Error::UnsupportedEvent`] for a value without an equivalent.
+fn unsupported(value: impl std::fmt::Debug) -> Error {
+    Error::UnsupportedEvent(format!("{value:?}"))
+}
+
+/// Convert a [`KeyEvent`] to a [`ct::KeyEvent`].
+impl From<KeyEvent> for ct::KeyEvent {
+    fn from(event: KeyEvent) -> Self {
+        ct::KeyEvent::new_with_kind(
+            event.code.into(),
+            modifiers(event.ctrl, event.alt, event.shift, event.meta),
+            event.kind.into(),
+        )
+    }
+}
+
+/// Convert a [`ct::KeyEvent`] to a [`KeyEvent`].
+///
+/// [`ct::KeyCode::BackTab`] is converted to [`KeyCode::Tab`] with shift.
+impl TryFrom<ct::KeyEvent> for KeyEvent {
+    type Error = Error;
+
+    fn try_from(event: ct::KeyEvent) -> Result<Self, Self::Error> {
+        Ok(KeyEvent {
+            code: event.code.try_into()?,
+            physical_key: None,
+            ctrl: event.modifiers.contains(ct::KeyModifiers::CONTROL),
+            alt: event.modifiers.contains(ct::KeyModifiers::ALT),
+            shift: event.modifiers.contains(ct::KeyModifiers::SHIFT)
+                || event.code == ct::KeyCode::BackTab,
+            meta: event.modifiers.contains(ct::KeyModifiers::SUPER),
+            kind: event.kind.into(),
+        })
+    }
+}
+
+/// Convert a [`KeyEventKind`] to a [`ct::KeyEventKind`].
+impl From<KeyEventKind> for ct::KeyEventKind {
+    fn from(kind: KeyEventKind) -> Self {
+        match kind {
+            KeyEventKind::Press => ct::KeyEventKind::Press,
+            KeyEventKind::Repeat => ct::KeyEventKind::Repeat,
+            KeyEventKind::Release => ct::KeyEventKind::Release,
+        }
+    }
+}
+
+/// Convert a [`ct::KeyEventKind`] to a [`KeyEventKind`].
+impl From<ct::KeyEventKind> for KeyEventKind {
+    fn from(kind: ct::KeyEventKind) -> Self {
+        match kind {
+            ct::KeyEventKind::Press => KeyEventKind::Press,
+            ct::KeyEventKind::Repeat => KeyEventKind::Repeat,
+            ct::KeyEventKind::Release => KeyEventKind::Release,
+        }
+    }
+}
+
+/// Convert a [`KeyCode`] to a [`ct::KeyCode`].
+///
+/// [`KeyCode::Unidentified`] is converted to [`ct::KeyCode::Null`].
+impl From<KeyCode> for ct::KeyCode {
+    fn from(code: KeyCode) -> Self {
+        match code {
+            KeyCode::Char(c) => ct::KeyCode::Char(c),
+            KeyCode::F(n) => ct::KeyCode::F(n),
+            KeyCode::Backspace => ct::KeyCode::Backspace,
+            KeyCode::Enter => ct::KeyCode::Enter,
+            KeyCode::Left => ct::KeyCode::Left,
+            KeyCode::Right => ct::KeyCode::Right,
+            KeyCode::Up => ct::KeyCode::Up,
+            KeyCode::Down => ct::KeyCode::Down,
+            KeyCode::Tab => ct::KeyCode::Tab,
+            KeyCode::Delete => ct::KeyCode::Delete,
+            KeyCode::Insert => ct::KeyCode::Insert,
+            KeyCode::Home => ct::KeyCode::Home,
+            KeyCode::End => ct::KeyCode::End,
+            KeyCode::PageUp => ct::KeyCode::PageUp,
+            KeyCode::PageDown => ct::KeyCode::PageDown,
+            KeyCode::Esc => ct::KeyCode::Esc,
+            KeyCode::CapsLock => ct::KeyCode::CapsLock,
+            KeyCode::ScrollLock => ct::KeyCode::ScrollLock,
+            KeyCode::NumLock => ct::KeyCode::NumLock,
+            KeyCode::PrintScreen => ct::KeyCode::PrintScreen,
+            KeyCode::Pause => ct::KeyCode::Pause,
+            KeyCode::Menu => ct::KeyCode::Menu,
+            KeyCode::KeypadBegin => ct::KeyCode::KeypadBegin,
+            KeyCode::Media(media) => ct::KeyCode::Media(media.into()),
+            KeyCode::Modifier(modifier) => ct::KeyCode::Modifier(modifier.into()),
+            KeyCode::Unidentified => ct::KeyCode::Null,
+        }
+    }
+}
+
+/// Convert a [`ct::KeyCode`] to a [`KeyCode`].
+///
+/// [`ct::KeyCode::Null`] is converted to [`KeyCode::Unidentified`], and
+/// [`ct::KeyCode::BackTab`] to [`KeyCode::Tab`].
+impl TryFrom<ct::KeyCode> for KeyCode {
+    type Error = Error;
+
+    fn try_from(code: ct::KeyCode) -> Result<Self, Self::Error> {
+        Ok(match code {
+            ct::KeyCode::Char(c) => KeyCode::Char(c),
+            ct::KeyCode::F(n) => KeyCode::F(n),
+            ct::KeyCode::Backspace => KeyCode::Backspace,
+            ct::KeyCode::Enter => KeyCode::Enter,
+            ct::KeyCode::Left => KeyCode::Left,
+            ct::KeyCode::Right => KeyCode::Right,
+            ct::KeyCode::Up => KeyCode::Up,
+            ct::KeyCode::Down => KeyCode::Down,
+            ct::KeyCode::Tab | ct::KeyCode::BackTab => KeyCode::Tab,
+            ct::KeyCode::Delete => KeyCode::Delete,
+            ct::KeyCode::Insert => KeyCode::Insert,
+            ct::KeyCode::Home => KeyCode::Home,
+            ct::KeyCode::End => KeyCode::End,
+            ct::KeyCode::PageUp => KeyCode::PageUp,
+            ct::KeyCode::PageDown => KeyCode::PageDown,
+            ct::KeyCode::Esc => KeyCode::Esc,
+            ct::KeyCode::CapsLock => KeyCode::CapsLock,
+            ct::KeyCode::ScrollLock => KeyCode::ScrollLock,
+            ct::KeyCode::NumLock => KeyCode::NumLock,
+            ct::KeyCode::PrintScreen => KeyCode::PrintScreen,
+            ct::KeyCode::Pause => KeyCode::Pause,
+            ct::KeyCode::Menu => KeyCode::Menu,
+            ct::KeyCode::KeypadBegin => KeyCode::KeypadBegin,
+            ct::KeyCode::Media(media) => KeyCode::Media(media.try_into()?),
+            ct::KeyCode::Modifier(modifier) => KeyCode::Modifier(modifier.try_into()?),
+            ct::KeyCode::Null => KeyCode::Unidentified,
+        })
+    }
+}
+
+/// Convert a [`MediaKeyCode`] to a [`ct::MediaKeyCode`].
+impl From<MediaKeyCode> for ct::MediaKeyCode {
+    fn from(code: MediaKeyCode) -> Self {
+        match code {
+            MediaKeyCode::Play => ct::MediaKeyCode::Play,
+            MediaKeyCode::Pause => ct::MediaKeyCode::Pause,
+            MediaKeyCode::PlayPause => ct::MediaKeyCode::PlayPause,
+            MediaKeyCode::Stop => ct::MediaKeyCode::Stop,
+            MediaKeyCode::FastForward => ct::MediaKeyCode::FastForward,
+            MediaKeyCode::Rewind => ct::MediaKeyCode::Rewind,
+            MediaKeyCode::TrackNext => ct::MediaKeyCode::TrackNext,
+            MediaKeyCode::TrackPrevious => ct::MediaKeyCode::TrackPrevious,
+            MediaKeyCode::Record => ct::MediaKeyCode::Record,
+            MediaKeyCode::LowerVolume => ct::MediaKeyCode::LowerVolume,
+            MediaKeyCode::RaiseVolume => ct::MediaKeyCode::RaiseVolume,
+            MediaKeyCode::MuteVolume => ct::MediaKeyCode::MuteVolume,
+        }
+    }
+}
+
+/// Convert a [`ct::MediaKeyCode`] to a [`MediaKeyCode`].
+impl TryFrom<ct::MediaKeyCode> for MediaKeyCode {
+    type Error = Error;
+
+    fn try_from(code: ct::MediaKeyCode) -> Result<Self, Self::Error> {
+        Ok(match code {
+            ct::MediaKeyCode::Play => MediaKeyCode::Play,
+            ct::MediaKeyCode::Pause => MediaKeyCode::Pause,
+            ct::MediaKeyCode::PlayPause => MediaKeyCode::PlayPause,
+            ct::MediaKeyCode::Stop => MediaKeyCode::Stop,
+            ct::MediaKeyCode::FastForward => MediaKeyCode::FastForward,
+            ct::MediaKeyCode::Rewind => MediaKeyCode::Rewind,
+            ct::MediaKeyCode::TrackNext => MediaKeyCode::TrackNext,
+            ct::MediaKeyCode::TrackPrevious => MediaKeyCode::TrackPrevious,
+            ct::MediaKeyCode::Record => MediaKeyCode::Record,
+            ct::MediaKeyCode::LowerVolume => MediaKeyCode::LowerVolume,
+            ct::MediaKeyCode::RaiseVolume => MediaKeyCode::RaiseVolume,
+            ct::MediaKeyCode::MuteVolume => MediaKeyCode::MuteVolume,
+            ct::MediaKeyCode::Reverse => return Err(unsupported(code)),
+        })
+    }
+}
+
+/// Convert a [`ModifierKeyCode`] to a [`ct::ModifierKeyCode`].
+impl From<ModifierKeyCode> for ct::ModifierKeyCode {
+    fn from(code: ModifierKeyCode) -> Self {
+        match code {
+            ModifierKeyCode::LeftShift => ct::ModifierKeyCode::LeftShift,
+            ModifierKeyCode::LeftControl => ct::ModifierKeyCode::LeftControl,
+            ModifierKeyCode::LeftAlt => ct::ModifierKeyCode::LeftAlt,
+            ModifierKeyCode::LeftSuper => ct::ModifierKeyCode::LeftSuper,
+            ModifierKeyCode::LeftHyper => ct::ModifierKeyCode::LeftHyper,
+            ModifierKeyCode::RightShift => ct::ModifierKeyCode::RightShift,
+            ModifierKeyCode::RightControl => ct::ModifierKeyCode::RightControl,
+            ModifierKeyCode::RightAlt => ct::ModifierKeyCode::RightAlt,
+            ModifierKeyCode::RightSuper => ct::ModifierKeyCode::RightSuper,
+            ModifierKeyCode::RightHyper => ct::ModifierKeyCode::RightHyper,
+            ModifierKeyCode::IsoLevel3Shift => ct::ModifierKeyCode::IsoLevel3Shift,
+        }
+    }
+}
+
+/// Convert a [`ct::ModifierKeyCode`] to a [`ModifierKeyCode`].
+impl TryFrom<ct::ModifierKeyCode> for ModifierKeyCode {
+    type Error = Error;
+
+    fn try_from(code: ct::ModifierKeyCode) -> Result<Self, Self::Error> {
+        Ok(match code {
+            ct::ModifierKeyCode::LeftShift => ModifierKeyCode::LeftShift,
+            ct::ModifierKeyCode::LeftControl => ModifierKeyCode::LeftControl,
+            ct::ModifierKeyCode::LeftAlt => ModifierKeyCode::LeftAlt,
+            ct::ModifierKeyCode::LeftSuper => ModifierKeyCode::LeftSuper,
+            ct::ModifierKeyCode::LeftHyper => ModifierKeyCode::LeftHyper,
+            ct::ModifierKeyCode::RightShift => ModifierKeyCode::RightShift,
+            ct::ModifierKeyCode::RightControl => ModifierKeyCode::RightControl,
+            ct::ModifierKeyCode::RightAlt => ModifierKeyCode::RightAlt,
+            ct::ModifierKeyCode::RightSuper => ModifierKeyCode::RightSuper,
+            ct::ModifierKeyCode::RightHyper => ModifierKeyCode::RightHyper,
+            ct::ModifierKeyCode::IsoLevel3Shift => ModifierKeyCode::IsoLevel3Shift,
+            ct::ModifierKeyCode::LeftMeta
+            | ct::ModifierKeyCode::RightMeta
+            | ct::ModifierKeyCode::IsoLevel5Shift => return Err(unsupported(code)),
+        })
+    }
+}
+
+/// Convert a [`MouseEvent`] to a [`ct::MouseEvent`].
+///
+/// Fails for clicks, enter/leave events and buttons other than left, right
+/// and middle.
+impl TryFrom<MouseEvent> for ct::MouseEvent {
+    type Error = Error;
+
+    fn try_from(event: MouseEvent) -> Result<Self, Self::Error> {
+        let kind = match event.kind {
+            MouseEventKind::Moved => ct::MouseEventKind::Moved,
+            MouseEventKind::ButtonDown(button) => ct::MouseEventKind::Down(button.try_into()?),
+            MouseEventKind::ButtonUp(button) => ct::MouseEventKind::Up(button.try_into()?),
+            MouseEventKind::Drag(button) => ct::MouseEventKind::Drag(button.try_into()?),
+            MouseEventKind::ScrollUp => ct::MouseEventKind::ScrollUp,
+            MouseEventKind::ScrollDown => ct::MouseEventKind::ScrollDown,
+            MouseEventKind::ScrollLeft => ct::MouseEventKind::ScrollLeft,
+            MouseEventKind::ScrollRight => ct::MouseEventKind::ScrollRight,
+            kind => return Err(unsupported(kind)),
+        };
+        Ok(ct::MouseEvent {
+            kind,
+            column: event.col,
+            row: event.row,
+            modifiers: modifiers(event.ctrl, event.alt, event.shift, event.meta),
+        })
+    }
+}
+
+/// Convert a [`ct::MouseEvent`] to a [`MouseEvent`].
+impl From<ct::MouseEvent> for MouseEvent {
+    fn from(event: ct::MouseEvent) -> Self {
+        let kind = match event.kind {
+            ct::MouseEventKind::Down(button) => MouseEventKind::ButtonDown(button.into()),
+            ct::MouseEventKind::Up(button) => MouseEventKind::ButtonUp(button.into()),
+            ct::MouseEventKind::Drag(button) => MouseEventKind::Drag(button.into()),
+            ct::MouseEventKind::Moved => MouseEventKind::Moved,
+            ct::MouseEventKind::ScrollDown => MouseEventKind::ScrollDown,
+            ct::MouseEventKind::ScrollUp => MouseEventKind::ScrollUp,
+            ct::MouseEventKind::ScrollLeft => MouseEventKind::ScrollLeft,
+            ct::MouseEventKind::ScrollRight => MouseEventKind::ScrollRight,
+        };
+        MouseEvent {
+            kind,
+            col: event.column,
+            row: event.row,
+            offset: None,
+            ctrl: event.modifiers.contains(ct::KeyModifiers::CONTROL),
+            alt: event.modifiers.contains(ct::KeyModifiers::ALT),
+            shift: event.modifiers.contains(ct::KeyModifiers::SHIFT),
+            meta: event.modifiers.contains(ct::KeyModifiers::SUPER),
+        }
+    }
+}
+
+/// Convert a [`MouseButton`] to a [`ct::MouseButton`].
+impl TryFrom<MouseButton> for ct::MouseButton {
+    type Error = Error;
+
+    fn try_from(button: MouseButton) -> Result<Self, Self::Error> {
+        match button {
+            MouseButton::Left => Ok(ct::MouseButton::Left),
+            MouseButton::Right => Ok(ct::MouseButton::Right),
+            MouseButton::Middle => Ok(ct::MouseButton::Middle),
+            button => Err(unsupported(button)),
+        }
+    }
+}
+
+/// Convert a [`ct::MouseButton`] to a [`MouseButton`].
+impl From<ct::MouseButton> for MouseButton {
+    fn from(button: ct::MouseButton) -> Self {
+        match button {
+            ct::MouseButton::Left => MouseButton::Left,
+            ct::MouseButton::Right => MouseButton::Right,
+            ct::MouseButton::Middle => MouseButton::Middle,
+        }
+    }
+}
+
+/// Convert an [`Event`] to a [`ct::Event`].
+///
+/// Fails for IME and gesture events, and for mouse events crossterm can't
+/// represent.
+impl TryFrom<Event> for ct::Event {
+    type Error = Error;
+
+    fn try_from(event: Event) -> Result<Self, Self::Error> {
+        Ok(match event {
+            Event::FocusGained => ct::Event::FocusGained,
+            Event::FocusLost => ct::Event::FocusLost,
+            Event::Key(key) => ct::Event::Key(key.into()),
+            Event::Mouse(mouse) => ct::Event::Mouse(mouse.try_into()?),
+            Event::Paste(text) => ct::Event::Paste(text),
+            Event::Resize(cols, rows) => ct::Event::Resize(cols, rows),
+            event @ (Event::Ime(_) | Event::Gesture(_) | Event::Gamepad(_)) => {
+                return Err(unsupported(event))
+            }
+        })
+    }
+}
+
+/// Convert a [`ct::Event`] to an [`Event`].
+impl TryFrom<ct::Event> for Event {
+    type Error = Error;
+
+    fn try_from(event: ct::Event) -> Result<Self, Self::Error> {
+        Ok(match event {
+            ct::Event::FocusGained => Event::FocusGained,
+            ct::Event::FocusLost => Event::FocusLost,
+            ct::Event::Key(key) => Event::Key(key.try_into()?),
+            ct::Event::Mouse(mouse) => Event::Mouse(mouse.into()),
+            ct::Event::Paste(text) => Event::Paste(text),
+            ct::Event::Resize(cols, rows) => Event::Resize(cols, rows),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{tests::key_event, FocusEvent};
+
+    #[test]
+    fn test_key_event_round_trip() {
+        let codes = [
+            KeyCode::Char('a'),
+            KeyCode::Char('Ä'),
+            KeyCode::F(12),
+            KeyCode::Enter,
+            KeyCode::Tab,
+            KeyCode::PageDown,
+            KeyCode::KeypadBegin,
+            KeyCode::Media(MediaKeyCode::PlayPause),
+            KeyCode::Modifier(ModifierKeyCode::RightAlt),
+            KeyCode::Unidentified,
+        ];
+        for code in codes {
+            for kind in [
+                KeyEventKind::Press,
+                KeyEventKind::Repeat,
+                KeyEventKind::Release,
+            ] {
+                let event = KeyEvent {
+                    kind,
+                    ..key_event(code.clone(), true, false, true, true)
+                };
+                let converted = ct::KeyEvent::from(event.clone());
+                assert_eq!(KeyEvent::try_from(converted).ok(), Some(event));
+            }
+        }
+    }
+
+    #[test]
+    fn test_key_event_from_crossterm() {
+        let event = ct::KeyEvent::new(ct::KeyCode::BackTab, ct::KeyModifiers::NONE);
+        assert_eq!(
+            KeyEvent::try_from(event).ok(),
+            Some(key_event(KeyCode::Tab, false, false, true, false))
+        );
+
+        let event = ct::KeyEvent::new(
+            ct::KeyCode::Media(ct::MediaKeyCode::Reverse),
+            ct::KeyModifiers::NONE,
+        );
+        assert!(KeyEvent::try_from(event).is_err());
+    }
+
+    #[test]
+    fn test_mouse_event_round_trip() {
+        let kinds = [
+            MouseEventKind::Moved,
+            MouseEventKind::ButtonDown(MouseButton::Left),
+            MouseEventKind::ButtonUp(MouseButton::Middle),
+            MouseEventKind::Drag(MouseButton::Right),
+            MouseEventKind::ScrollUp,
+            MouseEventKind::ScrollRight,
+        ];
+        for kind in kinds {
+            let event = MouseEvent {
+                kind,
+                col: 3,
+                offset: None,
+                row: 7,
+                ctrl: true,
+                alt: false,
+                shift: true,
+                meta: false,
+            };
+            let converted = ct::MouseEvent::try_from(event.clone()).ok();
+            assert_eq!(converted.map(MouseEvent::from), Some(event));
+        }
+
+        let unsupported = [
+            MouseEventKind::SingleClick(MouseButton::Left),
+            MouseEventKind::Entered,
+            MouseEventKind::ButtonDown(MouseButton::Back),
+        ];
+        for kind in unsupported {
+            let event = MouseEvent {
+                kind,
+                col: 0,
+                offset: None,
+                row: 0,
+                ctrl: false,
+                alt: false,
+                shift: false,
+                meta: false,
+            };
+            assert!(ct::MouseEvent::try_from(event).is_err());
+        }
+    }
+
+    #[test]
+    fn test_event_round_trip() {
+        let events = [
+            Event::from(FocusEvent::Gained),
+            Event::from(FocusEvent::Lost),
+            Event::Key(key_event(KeyCode::Esc, false, false, false, false)),
+            Event::Paste("rats\n".into()),
+            Event::Resize(80, 24),
+        ];
+        for event in events {
+            let converted = ct::Event::try_from(event.clone()).ok();
+            assert_eq!(converted.and_then(|e| Event::try_from(e).ok()), Some(event));
+        }
+        assert!(
+            ct::Event::try_from(Event::Ime(crate::event::ImeEvent::Commit("漢".into()))).is_err()
+        );
+    }
+}
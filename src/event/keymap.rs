@@ -0,0 +1,592 @@
+//! Key bindings parsed from strings like `"ctrl+s"`, and a [`Keymap`] that
+//! matches key events against them, including multi-key sequences.
+
+use std::{fmt, str::FromStr, time::Duration};
+
+use crate::{
+    error::Error,
+    event::{KeyCode, KeyEvent, KeyEventKind, KEY_NAMES, MAX_FUNCTION_KEY},
+};
+
+/// The default time to wait for the next key of a multi-key binding.
+const DEFAULT_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// Names of keys used when parsing and formatting bindings.
+///
+/// These take precedence over the browser key names in [`KEY_NAMES`], which
+/// are also accepted when parsing.
+const BINDING_KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("Space", KeyCode::Char(' ')),
+    ("Backspace", KeyCode::Backspace),
+    ("Enter", KeyCode::Enter),
+    ("Left", KeyCode::Left),
+    ("Right", KeyCode::Right),
+    ("Up", KeyCode::Up),
+    ("Down", KeyCode::Down),
+    ("Tab", KeyCode::Tab),
+    ("Delete", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Esc", KeyCode::Esc),
+    ("CapsLock", KeyCode::CapsLock),
+    ("ScrollLock", KeyCode::ScrollLock),
+    ("NumLock", KeyCode::NumLock),
+    ("PrintScreen", KeyCode::PrintScreen),
+    ("Pause", KeyCode::Pause),
+    ("Menu", KeyCode::Menu),
+    ("Return", KeyCode::Enter),
+];
+
+/// A single key with modifiers, e.g. `Ctrl+Shift+P`.
+///
+/// Bindings are parsed from strings of `+`-separated modifiers followed by a
+/// key. Modifiers are `Ctrl`, `Alt`, `Shift` and `Meta` (or `Cmd`, `Super`),
+/// and keys are either a single character or a name such as `Enter`, `Space`,
+/// `PageUp` or `F5`. Names are case-insensitive.
+///
+/// ```
+/// use ratzilla::event::{KeyBinding, KeyCode};
+///
+/// let binding: KeyBinding = "Ctrl+Shift+p".parse()?;
+/// assert_eq!(binding.code, KeyCode::Char('P'));
+/// assert_eq!(binding.to_string(), "Ctrl+Shift+P");
+/// # Ok::<(), ratzilla::error::Error>(())
+/// ```
+///
+/// Shift is encoded in the case of letters, and ignored for other characters,
+/// as it is needed to type them on some layouts: `G` is the same binding as
+/// `Shift+g`, and `?` matches a key event for `?` whether or not Shift is
+/// reported. Letters combined with Ctrl, Alt or Meta are case-insensitive
+/// unless Shift is given, so `Ctrl+C` is the same binding as `Ctrl+c`.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct KeyBinding {
+    /// The key code.
+    pub code: KeyCode,
+    /// Whether the control key is pressed.
+    pub ctrl: bool,
+    /// Whether the alt key is pressed.
+    pub alt: bool,
+    /// Whether the shift key is pressed.
+    ///
+    /// This is always `false` for [`KeyCode::Char`].
+    pub shift: bool,
+    /// Whether the meta key is pressed.
+    pub meta: bool,
+}
+
+impl KeyBinding {
+    /// Constructs a new [`KeyBinding`] for the given key without modifiers.
+    pub fn new(code: KeyCode) -> Self {
+        Self {
+            code,
+            ctrl: false,
+            alt: false,
+            shift: false,
+            meta: false,
+        }
+        .normalized()
+    }
+
+    /// Adds the control modifier.
+    pub fn ctrl(mut self) -> Self {
+        self.ctrl = true;
+        self
+    }
+
+    /// Adds the alt modifier.
+    pub fn alt(mut self) -> Self {
+        self.alt = true;
+        self
+    }
+
+    /// Adds the shift modifier.
+    pub fn shift(mut self) -> Self {
+        self.shift = true;
+        self.normalized()
+    }
+
+    /// Adds the meta modifier.
+    pub fn meta(mut self) -> Self {
+        self.meta = true;
+        self
+    }
+
+    /// Returns whether the key event is for this binding.
+    ///
+    /// The [`KeyEventKind`] of the event is not taken into account.
+    pub fn matches(&self, event: &KeyEvent) -> bool {
+        *self == KeyBinding::from(event)
+    }
+
+    /// Returns whether Ctrl, Alt or Meta is pressed.
+    fn has_command_modifier(&self) -> bool {
+        self.ctrl || self.alt || self.meta
+    }
+
+    /// Converts a character to lowercase.
+    fn lowercased(mut self) -> Self {
+        if let KeyCode::Char(c) = self.code {
+            self.code = KeyCode::Char(c.to_lowercase().next().unwrap_or(c));
+        }
+        self
+    }
+
+    /// Moves the shift modifier of a character into its case.
+    fn normalized(mut self) -> Self {
+        if let KeyCode::Char(c) = self.code {
+            if self.shift && c.is_lowercase() {
+                self.code = KeyCode::Char(c.to_uppercase().next().unwrap_or(c));
+            }
+            self.shift = false;
+        }
+        self
+    }
+}
+
+impl From<&KeyEvent> for KeyBinding {
+    fn from(event: &KeyEvent) -> Self {
+        let binding = Self {
+            code: event.code.clone(),
+            ctrl: event.ctrl,
+            alt: event.alt,
+            shift: event.shift,
+            meta: event.meta,
+        };
+        // Letters are reported in uppercase while Caps Lock is on
+        if binding.has_command_modifier() && !binding.shift {
+            binding.lowercased()
+        } else {
+            binding.normalized()
+        }
+    }
+}
+
+impl FromStr for KeyBinding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::InvalidKeyBinding(s.to_string());
+        let (modifiers, key) = match s.strip_suffix("++") {
+            Some(modifiers) => (modifiers, "+"),
+            None if s == "+" => ("", s),
+            None => s.rsplit_once('+').unwrap_or(("", s)),
+        };
+
+        let mut binding = KeyBinding::new(parse_key(key).ok_or_else(invalid)?);
+        let mut shift = false;
+        for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+            binding = match modifier.to_lowercase().as_str() {
+                "ctrl" | "control" => binding.ctrl(),
+                "alt" | "option" => binding.alt(),
+                "shift" => {
+                    shift = true;
+                    binding.shift()
+                }
+                "meta" | "cmd" | "super" => binding.meta(),
+                _ => return Err(invalid()),
+            };
+        }
+        if binding.has_command_modifier() && !shift {
+            binding = binding.lowercased();
+        }
+        Ok(binding)
+    }
+}
+
+/// Parses the key of a binding.
+fn parse_key(key: &str) -> Option<KeyCode> {
+    let mut chars = key.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+
+    let named = BINDING_KEY_NAMES
+        .iter()
+        .chain(KEY_NAMES)
+        .find(|(name, _)| name.eq_ignore_ascii_case(key));
+    if let Some((_, code)) = named {
+        return Some(code.clone());
+    }
+
+    key.strip_prefix(['F', 'f'])
+        .and_then(|n| n.parse::<u8>().ok())
+        .filter(|n| (1..=MAX_FUNCTION_KEY).contains(n))
+        .map(KeyCode::F)
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            f.write_str("Ctrl+")?;
+        }
+        if self.alt {
+            f.write_str("Alt+")?;
+        }
+        let command = self.has_command_modifier();
+        let shifted_letter = matches!(self.code, KeyCode::Char(c) if c.is_uppercase());
+        if self.shift || (command && shifted_letter) {
+            f.write_str("Shift+")?;
+        }
+        if self.meta {
+            f.write_str("Meta+")?;
+        }
+        match self.code {
+            // letters are shown in upper case along with Ctrl, Alt and Meta
+            KeyCode::Char(c) if command && c.is_lowercase() => {
+                write!(f, "{}", c.to_uppercase())
+            }
+            _ => fmt::Display::fmt(&self.code, f),
+        }
+    }
+}
+
+impl fmt::Display for KeyCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let named = BINDING_KEY_NAMES
+            .iter()
+            .chain(KEY_NAMES)
+            .find(|(_, code)| code == self);
+        match (self, named) {
+            (_, Some((name, _))) => f.write_str(name),
+            (KeyCode::Char(c), None) => write!(f, "{c}"),
+            (KeyCode::F(n), None) => write!(f, "F{n}"),
+            (KeyCode::Modifier(modifier), None) => write!(f, "{modifier:?}"),
+            (code, None) => write!(f, "{code:?}"),
+        }
+    }
+}
+
+/// Formats the key event as a [`KeyBinding`], e.g. `Ctrl+C`.
+impl fmt::Display for KeyEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        KeyBinding::from(self).fmt(f)
+    }
+}
+
+/// A sequence of [`KeyBinding`]s, e.g. `g g`.
+///
+/// Sequences are parsed from whitespace-separated bindings.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct KeySequence(pub Vec<KeyBinding>);
+
+impl FromStr for KeySequence {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let keys = s
+            .split_whitespace()
+            .map(KeyBinding::from_str)
+            .collect::<Result<Vec<_>, _>>()?;
+        if keys.is_empty() {
+            return Err(Error::InvalidKeyBinding(s.to_string()));
+        }
+        Ok(Self(keys))
+    }
+}
+
+impl fmt::Display for KeySequence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, key) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            key.fmt(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// Maps key sequences to actions.
+///
+/// ```no_run
+/// use ratzilla::event::Keymap;
+///
+/// #[derive(Clone, Copy, Debug, PartialEq)]
+/// enum Action {
+///     Quit,
+///     Palette,
+///     Top,
+/// }
+///
+/// let mut keymap = Keymap::new();
+/// keymap.bind("Ctrl+c", Action::Quit)?;
+/// keymap.bind("Ctrl+Shift+P", Action::Palette)?;
+/// keymap.bind("g g", Action::Top)?;
+///
+/// // in the key event handler:
+/// # let key_event = ratzilla::event::KeyEvent {
+/// #     code: ratzilla::event::KeyCode::Char('c'),
+/// #     physical_key: None,
+/// #     ctrl: true,
+/// #     alt: false,
+/// #     shift: false,
+/// #     meta: false,
+/// #     kind: ratzilla::event::KeyEventKind::Press,
+/// # };
+/// match keymap.handle(&key_event) {
+///     Some(Action::Quit) => { /* ... */ }
+///     Some(action) => { /* ... */ }
+///     None => {}
+/// }
+///
+/// // list the bindings on a help screen
+/// for (keys, action) in keymap.iter() {
+///     println!("{keys:<16} {action:?}");
+/// }
+/// # Ok::<(), ratzilla::error::Error>(())
+/// ```
+///
+/// Keys of a multi-key sequence must follow each other within the
+/// [timeout](Keymap::timeout). A key that doesn't continue the pending
+/// sequence starts a new one. Release events and modifier keys pressed on
+/// their own are ignored.
+#[derive(Debug, Clone)]
+pub struct Keymap<A> {
+    /// The bindings, in the order they were added.
+    bindings: Vec<(KeySequence, A)>,
+    /// The keys of the sequence that is being typed.
+    pending: Vec<KeyBinding>,
+    /// The time of the last pending key, in milliseconds.
+    last_key_time: f64,
+    /// The time to wait for the next key of a sequence.
+    timeout: Duration,
+}
+
+impl<A> Default for Keymap<A> {
+    fn default() -> Self {
+        Self {
+            bindings: Vec::new(),
+            pending: Vec::new(),
+            last_key_time: 0.0,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl<A> Keymap<A> {
+    /// Constructs a new, empty [`Keymap`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the time to wait for the next key of a multi-key sequence.
+    ///
+    /// Defaults to one second.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Binds a key sequence, such as `"Ctrl+Shift+P"` or `"g g"`, to an action.
+    ///
+    /// Binding the same sequence again replaces its action. Returns an error
+    /// if the sequence can't be parsed, or if it is a prefix of another bound
+    /// sequence (or the other way around), as one of them could never match.
+    pub fn bind(&mut self, keys: &str, action: A) -> Result<(), Error> {
+        self.bind_sequence(keys.parse()?, action)
+    }
+
+    /// Binds a parsed [`KeySequence`] to an action.
+    ///
+    /// See [`Keymap::bind`].
+    pub fn bind_sequence(&mut self, keys: KeySequence, action: A) -> Result<(), Error> {
+        if let Some(index) = self.bindings.iter().position(|(k, _)| *k == keys) {
+            self.bindings[index].1 = action;
+            return Ok(());
+        }
+        let conflict = self
+            .bindings
+            .iter()
+            .find(|(bound, _)| bound.0.starts_with(&keys.0) || keys.0.starts_with(&bound.0));
+        if let Some((bound, _)) = conflict {
+            return Err(Error::InvalidKeyBinding(format!(
+                "{keys} conflicts with {bound}"
+            )));
+        }
+        self.bindings.push((keys, action));
+        Ok(())
+    }
+
+    /// Removes the binding of a key sequence, returning its action.
+    pub fn unbind(&mut self, keys: &KeySequence) -> Option<A> {
+        let index = self.bindings.iter().position(|(k, _)| k == keys)?;
+        self.pending.clear();
+        Some(self.bindings.remove(index).1)
+    }
+
+    /// Returns an iterator over the bindings and their actions, in the order
+    /// they were added.
+    pub fn iter(&self) -> impl Iterator<Item = (&KeySequence, &A)> {
+        self.bindings.iter().map(|(keys, action)| (keys, action))
+    }
+
+    /// Returns the keys of the sequence that is being typed, e.g. to show
+    /// `g` in a status bar while waiting for the next key of `g g`.
+    pub fn pending(&self) -> &[KeyBinding] {
+        &self.pending
+    }
+
+    /// Handles a key event, returning the action of the sequence it completes.
+    pub fn handle(&mut self, event: &KeyEvent) -> Option<&A> {
+        self.handle_at(event, web_sys::js_sys::Date::now())
+    }
+
+    /// Handles a key event that happened at the given time, in milliseconds.
+    fn handle_at(&mut self, event: &KeyEvent, now: f64) -> Option<&A> {
+        if event.kind == KeyEventKind::Release || matches!(event.code, KeyCode::Modifier(_)) {
+            return None;
+        }
+        if now - self.last_key_time > self.timeout.as_secs_f64() * 1000.0 {
+            self.pending.clear();
+        }
+        self.last_key_time = now;
+
+        let key = KeyBinding::from(event);
+        self.pending.push(key.clone());
+        if !self.is_prefix(&self.pending) {
+            // start a new sequence with this key
+            self.pending.clear();
+            self.pending.push(key);
+            if !self.is_prefix(&self.pending) {
+                self.pending.clear();
+                return None;
+            }
+        }
+
+        let index = self
+            .bindings
+            .iter()
+            .position(|(keys, _)| keys.0 == self.pending)?;
+        self.pending.clear();
+        Some(&self.bindings[index].1)
+    }
+
+    /// Returns whether the keys are a prefix of a bound sequence.
+    fn is_prefix(&self, keys: &[KeyBinding]) -> bool {
+        self.bindings
+            .iter()
+            .any(|(bound, _)| bound.0.starts_with(keys))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{tests::key_event, ModifierKeyCode};
+
+    fn press(code: KeyCode, ctrl: bool, shift: bool) -> KeyEvent {
+        key_event(code, ctrl, false, shift, false)
+    }
+
+    #[test]
+    fn test_parse_key_binding() {
+        let binding: KeyBinding = "Ctrl+Shift+P".parse().expect("valid binding");
+        assert_eq!(binding, KeyBinding::new(KeyCode::Char('P')).ctrl());
+        assert_eq!("ctrl+shift+p".parse::<KeyBinding>().ok(), Some(binding));
+
+        let cases = [
+            ("Ctrl++", KeyBinding::new(KeyCode::Char('+')).ctrl()),
+            ("+", KeyBinding::new(KeyCode::Char('+'))),
+            ("Shift+Tab", KeyBinding::new(KeyCode::Tab).shift()),
+            ("Alt+F12", KeyBinding::new(KeyCode::F(12)).alt()),
+            ("F24", KeyBinding::new(KeyCode::F(24))),
+            ("Cmd+space", KeyBinding::new(KeyCode::Char(' ')).meta()),
+            ("Escape", KeyBinding::new(KeyCode::Esc)),
+            ("ArrowUp", KeyBinding::new(KeyCode::Up)),
+            ("G", KeyBinding::new(KeyCode::Char('g')).shift()),
+            ("Ctrl+C", KeyBinding::new(KeyCode::Char('c')).ctrl()),
+            ("Alt+Shift+c", KeyBinding::new(KeyCode::Char('C')).alt()),
+        ];
+        for (s, expected) in cases {
+            assert_eq!(s.parse::<KeyBinding>().ok(), Some(expected), "{s}");
+        }
+
+        for s in ["", "Ctrl+", "Hyper+a", "F0", "F25", "F255", "Foo"] {
+            assert!(s.parse::<KeyBinding>().is_err(), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_format_key_binding() {
+        let cases = [
+            (press(KeyCode::Char('P'), true, true), "Ctrl+Shift+P"),
+            (press(KeyCode::Char('?'), false, true), "?"),
+            (press(KeyCode::Char('c'), true, false), "Ctrl+C"),
+            (press(KeyCode::Char('G'), false, true), "G"),
+            (press(KeyCode::Char('g'), false, false), "g"),
+            (press(KeyCode::Char(' '), true, false), "Ctrl+Space"),
+            (press(KeyCode::Char('+'), true, false), "Ctrl++"),
+            (press(KeyCode::Tab, false, true), "Shift+Tab"),
+            (press(KeyCode::Esc, false, false), "Esc"),
+            (
+                key_event(KeyCode::F(5), true, true, false, true),
+                "Ctrl+Alt+Meta+F5",
+            ),
+        ];
+        for (event, expected) in cases {
+            assert_eq!(event.to_string(), expected);
+            // formatted bindings parse back to the same binding
+            let binding: KeyBinding = expected.parse().expect("valid binding");
+            assert!(binding.matches(&event), "{expected}");
+        }
+    }
+
+    #[test]
+    fn test_keymap() {
+        let mut keymap = Keymap::new().timeout(Duration::from_millis(500));
+        keymap.bind("Ctrl+c", 1).expect("valid binding");
+        keymap.bind("g g", 2).expect("valid binding");
+        keymap.bind("g e", 3).expect("valid binding");
+        assert!(keymap.bind("g", 4).is_err());
+        assert!(keymap.bind("Ctrl+c x", 4).is_err());
+
+        let g = press(KeyCode::Char('g'), false, false);
+        let e = press(KeyCode::Char('e'), false, false);
+        let ctrl_c = press(KeyCode::Char('c'), true, false);
+
+        assert_eq!(keymap.handle_at(&ctrl_c, 0.0), Some(&1));
+        // with Caps Lock on, the letter is uppercase but Shift isn't held
+        let caps_lock_ctrl_c = press(KeyCode::Char('C'), true, false);
+        assert_eq!(keymap.handle_at(&caps_lock_ctrl_c, 50.0), Some(&1));
+        let ctrl_shift_c = press(KeyCode::Char('C'), true, true);
+        assert_eq!(keymap.handle_at(&ctrl_shift_c, 60.0), None);
+        assert_eq!(keymap.handle_at(&g, 100.0), None);
+        assert_eq!(keymap.pending(), [KeyBinding::new(KeyCode::Char('g'))]);
+        assert_eq!(keymap.handle_at(&g, 200.0), Some(&2));
+        assert!(keymap.pending().is_empty());
+
+        // modifiers and releases don't break a sequence
+        let shift = press(KeyCode::Modifier(ModifierKeyCode::LeftShift), false, true);
+        let release = KeyEvent {
+            kind: KeyEventKind::Release,
+            ..g.clone()
+        };
+        assert_eq!(keymap.handle_at(&g, 300.0), None);
+        assert_eq!(keymap.handle_at(&release, 350.0), None);
+        assert_eq!(keymap.handle_at(&shift, 400.0), None);
+        assert_eq!(keymap.handle_at(&e, 500.0), Some(&3));
+
+        // the sequence times out
+        assert_eq!(keymap.handle_at(&g, 1000.0), None);
+        assert_eq!(keymap.handle_at(&e, 1600.0), None);
+        assert!(keymap.pending().is_empty());
+
+        // a key that breaks the sequence starts a new one
+        assert_eq!(keymap.handle_at(&g, 2000.0), None);
+        assert_eq!(keymap.handle_at(&ctrl_c, 2100.0), Some(&1));
+
+        // rebinding replaces the action
+        keymap.bind("ctrl+c", 5).expect("valid binding");
+        assert_eq!(keymap.handle_at(&ctrl_c, 3000.0), Some(&5));
+        assert_eq!(
+            keymap
+                .iter()
+                .map(|(keys, _)| keys.to_string())
+                .collect::<Vec<_>>(),
+            ["Ctrl+C", "g g", "g e"]
+        );
+    }
+}
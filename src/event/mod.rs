@@ -0,0 +1,1047 @@
+#[cfg(feature = "crossterm")]
+mod crossterm;
+mod keymap;
+mod stream;
+#[cfg(feature = "tui-textarea")]
+mod tui_textarea;
+
+pub use keymap::{KeyBinding, KeySequence, Keymap};
+pub use stream::{EventStream, PendingEvents};
+
+/// An input event.
+///
+/// This mirrors the shape of crossterm's `Event`, so that the event loops of
+/// native ratatui applications can be ported without changes. Events are
+/// delivered via [`WebRenderer::on_event`].
+///
+/// Enabling the `crossterm` feature adds conversions to and from crossterm's
+/// events, and the `tui-textarea` feature adds conversions to and from
+/// `tui_textarea::Input`. There are no termion conversions, as termion doesn't
+/// compile for WebAssembly.
+///
+/// [`WebRenderer::on_event`]: crate::WebRenderer::on_event
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// The terminal gained focus.
+    FocusGained,
+    /// The terminal lost focus.
+    FocusLost,
+    /// A key event.
+    Key(KeyEvent),
+    /// A mouse event, including touch input mapped to the mouse.
+    Mouse(MouseEvent),
+    /// Text was pasted into the terminal.
+    Paste(String),
+    /// The terminal was resized to the given number of columns and rows.
+    Resize(u16, u16),
+    /// An IME composition event.
+    Ime(ImeEvent),
+    /// A touch gesture.
+    Gesture(GestureEvent),
+    /// A gamepad event.
+    Gamepad(GamepadEvent),
+}
+
+impl From<KeyEvent> for Event {
+    fn from(event: KeyEvent) -> Self {
+        Event::Key(event)
+    }
+}
+
+impl From<MouseEvent> for Event {
+    fn from(event: MouseEvent) -> Self {
+        Event::Mouse(event)
+    }
+}
+
+impl From<FocusEvent> for Event {
+    fn from(event: FocusEvent) -> Self {
+        match event {
+            FocusEvent::Gained => Event::FocusGained,
+            FocusEvent::Lost => Event::FocusLost,
+        }
+    }
+}
+
+impl From<ResizeEvent> for Event {
+    fn from(event: ResizeEvent) -> Self {
+        Event::Resize(event.cols, event.rows)
+    }
+}
+
+impl From<ImeEvent> for Event {
+    fn from(event: ImeEvent) -> Self {
+        Event::Ime(event)
+    }
+}
+
+impl From<GestureEvent> for Event {
+    fn from(event: GestureEvent) -> Self {
+        Event::Gesture(event)
+    }
+}
+
+impl From<GamepadEvent> for Event {
+    fn from(event: GamepadEvent) -> Self {
+        Event::Gamepad(event)
+    }
+}
+
+/// A key event.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct KeyEvent {
+    /// The key code.
+    pub code: KeyCode,
+    /// The physical key that was pressed, independent of the keyboard layout.
+    ///
+    /// This is the value of [`web_sys::KeyboardEvent::code`], e.g. `"KeyA"` or
+    /// `"Digit1"`, and is `None` if the browser doesn't report one (as is the
+    /// case for some virtual keyboards). Use it to bind keys by position,
+    /// e.g. WASD movement that also works on AZERTY and Dvorak layouts.
+    ///
+    /// See <https://developer.mozilla.org/en-US/docs/Web/API/UI_Events/Keyboard_event_code_values>.
+    pub physical_key: Option<String>,
+    /// Whether the control key is pressed.
+    pub ctrl: bool,
+    /// Whether the alt key is pressed.
+    pub alt: bool,
+    /// Whether the shift key is pressed.
+    pub shift: bool,
+    /// Whether the meta key is pressed (Command on macOS, Windows key elsewhere).
+    pub meta: bool,
+    /// The kind of key event (press, repeat or release).
+    pub kind: KeyEventKind,
+}
+
+impl KeyEvent {
+    /// Returns whether the key is on the numeric keypad.
+    ///
+    /// Keypad keys have the same [`KeyCode`] as their equivalents on the
+    /// main keyboard, so this tells them apart by their [`physical_key`].
+    ///
+    /// [`physical_key`]: KeyEvent::physical_key
+    pub fn is_keypad(&self) -> bool {
+        self.physical_key
+            .as_deref()
+            .is_some_and(|key| key.starts_with("Numpad"))
+    }
+}
+
+/// The kind of a [`KeyEvent`].
+///
+/// Release events are only reported if enabled in the backend options, e.g.
+/// with [`DomBackendOptions::report_key_release_events`].
+///
+/// [`DomBackendOptions::report_key_release_events`]: crate::backend::dom::DomBackendOptions::report_key_release_events
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum KeyEventKind {
+    /// The key was pressed.
+    Press,
+    /// The key is held down and the OS auto-repeat fired.
+    Repeat,
+    /// The key was released.
+    Release,
+}
+
+/// An IME (input method editor) composition event.
+///
+/// Input methods for e.g. Chinese, Japanese and Korean compose text over
+/// several key strokes before committing it. While composing, the key events
+/// consumed by the input method are not reported as [`KeyEvent`]s.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum ImeEvent {
+    /// The text being composed changed.
+    ///
+    /// Applications should display `preedit` at the cursor position until the
+    /// composition is committed.
+    Composition {
+        /// The text being composed.
+        preedit: String,
+        /// The position of the caret within `preedit`, in chars.
+        cursor: Option<usize>,
+    },
+    /// The composition ended and the text should be inserted.
+    ///
+    /// The text is empty if the composition was cancelled.
+    Commit(String),
+}
+
+/// A change in the size of the terminal.
+///
+/// Reported after the backend has resized its grid, and before the next frame
+/// is drawn with the new size.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct ResizeEvent {
+    /// The number of columns in the terminal grid.
+    pub cols: u16,
+    /// The number of rows in the terminal grid.
+    pub rows: u16,
+    /// The width of the terminal, in CSS pixels.
+    pub width: u16,
+    /// The height of the terminal, in CSS pixels.
+    pub height: u16,
+}
+
+/// Convert a [`ratatui::backend::WindowSize`] to a [`ResizeEvent`].
+impl From<ratatui::backend::WindowSize> for ResizeEvent {
+    fn from(size: ratatui::backend::WindowSize) -> Self {
+        ResizeEvent {
+            cols: size.columns_rows.width,
+            rows: size.columns_rows.height,
+            width: size.pixels.width,
+            height: size.pixels.height,
+        }
+    }
+}
+
+/// A change of keyboard focus of the terminal.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum FocusEvent {
+    /// The terminal gained focus.
+    Gained,
+    /// The terminal lost focus.
+    Lost,
+}
+
+/// A touch gesture with terminal grid coordinates.
+///
+/// Gestures are reported in addition to the [`MouseEvent`]s that touch input
+/// is mapped to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GestureEvent {
+    /// A quick single-finger swipe.
+    Swipe {
+        /// The direction of the swipe.
+        direction: SwipeDirection,
+        /// The column where the swipe started.
+        col: u16,
+        /// The row where the swipe started.
+        row: u16,
+    },
+    /// Two fingers moved towards or away from each other.
+    Pinch {
+        /// The distance between the fingers relative to when the pinch
+        /// started; greater than `1.0` when zooming in.
+        scale: f64,
+        /// The column of the midpoint between the fingers.
+        col: u16,
+        /// The row of the midpoint between the fingers.
+        row: u16,
+    },
+}
+
+/// The direction of a [`GestureEvent::Swipe`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum SwipeDirection {
+    /// Swiped towards the top of the screen.
+    Up,
+    /// Swiped towards the bottom of the screen.
+    Down,
+    /// Swiped towards the left of the screen.
+    Left,
+    /// Swiped towards the right of the screen.
+    Right,
+}
+
+/// A gamepad event.
+///
+/// Gamepads are polled on every animation frame of the render loop, so events
+/// are only reported while it is running. Browsers only expose a gamepad to the page once
+/// one of its buttons has been pressed, which is when it is reported as
+/// connected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GamepadEvent {
+    /// The index of the gamepad, which identifies it while it is connected.
+    pub gamepad: u32,
+    /// The kind of gamepad event.
+    pub kind: GamepadEventKind,
+}
+
+/// The kind of a [`GamepadEvent`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum GamepadEventKind {
+    /// The gamepad was connected.
+    Connected {
+        /// The name of the gamepad, as reported by the browser.
+        id: String,
+        /// Whether the browser maps the gamepad to the standard layout, so
+        /// that its buttons and axes are identified. Otherwise, they are
+        /// reported as [`GamepadButton::Other`] and [`GamepadAxis::Other`].
+        standard: bool,
+    },
+    /// The gamepad was disconnected.
+    Disconnected,
+    /// A button was pressed.
+    ButtonPressed(GamepadButton),
+    /// A button was released.
+    ButtonReleased(GamepadButton),
+    /// An axis moved to the given value, between `-1.0` and `1.0`.
+    ///
+    /// Values within the dead zone are reported as `0.0`, and the remaining
+    /// range is scaled to start at zero.
+    AxisChanged(GamepadAxis, f64),
+}
+
+/// A gamepad button in the
+/// [standard layout](https://w3c.github.io/gamepad/#remapping).
+///
+/// Face buttons are named by position, as their labels differ between
+/// controllers: [`GamepadButton::South`] is A on an Xbox controller and Cross
+/// on a PlayStation controller.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum GamepadButton {
+    /// The bottom face button.
+    South,
+    /// The right face button.
+    East,
+    /// The left face button.
+    West,
+    /// The top face button.
+    North,
+    /// The left shoulder button.
+    LeftBumper,
+    /// The right shoulder button.
+    RightBumper,
+    /// The left trigger.
+    LeftTrigger,
+    /// The right trigger.
+    RightTrigger,
+    /// The select (back, view or share) button.
+    Select,
+    /// The start (menu or options) button.
+    Start,
+    /// Pressing the left stick.
+    LeftStick,
+    /// Pressing the right stick.
+    RightStick,
+    /// Up on the directional pad.
+    DPadUp,
+    /// Down on the directional pad.
+    DPadDown,
+    /// Left on the directional pad.
+    DPadLeft,
+    /// Right on the directional pad.
+    DPadRight,
+    /// The center (home or guide) button.
+    Home,
+    /// A button outside of the standard layout, by index.
+    Other(u32),
+}
+
+impl GamepadButton {
+    /// The buttons of the standard layout, by index.
+    const STANDARD: [GamepadButton; 17] = [
+        GamepadButton::South,
+        GamepadButton::East,
+        GamepadButton::West,
+        GamepadButton::North,
+        GamepadButton::LeftBumper,
+        GamepadButton::RightBumper,
+        GamepadButton::LeftTrigger,
+        GamepadButton::RightTrigger,
+        GamepadButton::Select,
+        GamepadButton::Start,
+        GamepadButton::LeftStick,
+        GamepadButton::RightStick,
+        GamepadButton::DPadUp,
+        GamepadButton::DPadDown,
+        GamepadButton::DPadLeft,
+        GamepadButton::DPadRight,
+        GamepadButton::Home,
+    ];
+
+    /// Returns the button at the given index of a gamepad, which is only
+    /// identified if the gamepad uses the standard layout.
+    pub fn from_index(index: u32, standard: bool) -> Self {
+        match Self::STANDARD.get(index as usize) {
+            Some(button) if standard => *button,
+            _ => GamepadButton::Other(index),
+        }
+    }
+}
+
+/// A gamepad axis in the
+/// [standard layout](https://w3c.github.io/gamepad/#remapping).
+///
+/// Horizontal axes are negative to the left, and vertical axes are negative
+/// upwards.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum GamepadAxis {
+    /// The horizontal axis of the left stick.
+    LeftStickX,
+    /// The vertical axis of the left stick.
+    LeftStickY,
+    /// The horizontal axis of the right stick.
+    RightStickX,
+    /// The vertical axis of the right stick.
+    RightStickY,
+    /// An axis outside of the standard layout, by index.
+    Other(u32),
+}
+
+impl GamepadAxis {
+    /// The axes of the standard layout, by index.
+    const STANDARD: [GamepadAxis; 4] = [
+        GamepadAxis::LeftStickX,
+        GamepadAxis::LeftStickY,
+        GamepadAxis::RightStickX,
+        GamepadAxis::RightStickY,
+    ];
+
+    /// Returns the axis at the given index of a gamepad, which is only
+    /// identified if the gamepad uses the standard layout.
+    pub fn from_index(index: u32, standard: bool) -> Self {
+        match Self::STANDARD.get(index as usize) {
+            Some(axis) if standard => *axis,
+            _ => GamepadAxis::Other(index),
+        }
+    }
+}
+
+/// A mouse event with terminal grid coordinates.
+///
+/// Coordinates are reported as terminal cell positions (`col`, `row`),
+/// not raw pixel coordinates. The origin (0, 0) is the top-left cell
+/// of the terminal grid. The position of the pointer within the cell is
+/// available from [`MouseEvent::offset`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct MouseEvent {
+    /// The type of mouse event that occurred.
+    pub kind: MouseEventKind,
+    /// The column (x-coordinate) in the terminal grid.
+    pub col: u16,
+    /// The row (y-coordinate) in the terminal grid.
+    pub row: u16,
+    /// The position of the pointer within the cell.
+    ///
+    /// This is `None` for touch input and for events converted from other
+    /// libraries.
+    pub offset: Option<CellOffset>,
+    /// Whether the control key is pressed.
+    pub ctrl: bool,
+    /// Whether the alt key is pressed.
+    pub alt: bool,
+    /// Whether the shift key is pressed.
+    pub shift: bool,
+    /// Whether the meta key is pressed (Command on macOS, Windows key elsewhere).
+    pub meta: bool,
+}
+
+impl MouseEvent {
+    /// Returns the position of the pointer in the terminal grid, at sub-cell
+    /// resolution, as `(x, y)`.
+    ///
+    /// The integer part is the column and row, and the fractional part the
+    /// position within the cell. This can be used to map mouse events onto
+    /// a ratatui [`Canvas`] drawn with braille or half-block markers. Without
+    /// an [`offset`](MouseEvent::offset), the top-left corner of the cell is
+    /// returned.
+    ///
+    /// [`Canvas`]: ratatui::widgets::canvas::Canvas
+    pub fn position(&self) -> (f64, f64) {
+        let (x, y) = self.offset.map_or((0.0, 0.0), |offset| offset.fraction());
+        (self.col as f64 + x, self.row as f64 + y)
+    }
+}
+
+/// The number of sub-pixel units per pixel in a [`CellOffset`].
+const CELL_OFFSET_UNITS_PER_PX: f64 = 64.0;
+
+/// The position of the mouse pointer within a terminal cell.
+///
+/// Offsets are measured from the top-left corner of the cell, in the same CSS
+/// pixels as [`CellSized::cell_size_css_px`], and in device pixels. They are
+/// stored with a precision of 1/64 pixel, so that [`MouseEvent`] can still
+/// implement [`Eq`].
+///
+/// [`CellSized::cell_size_css_px`]: crate::CellSized::cell_size_css_px
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct CellOffset {
+    /// The horizontal offset, in sub-pixel units of CSS pixels.
+    x: u32,
+    /// The vertical offset, in sub-pixel units of CSS pixels.
+    y: u32,
+    /// The horizontal offset, in sub-pixel units of device pixels.
+    device_x: u32,
+    /// The vertical offset, in sub-pixel units of device pixels.
+    device_y: u32,
+    /// The width of a cell, in sub-pixel units of CSS pixels.
+    cell_width: u32,
+    /// The height of a cell, in sub-pixel units of CSS pixels.
+    cell_height: u32,
+}
+
+impl CellOffset {
+    /// Creates a new [`CellOffset`] from an offset and cell size in CSS
+    /// pixels, and the number of device pixels per CSS pixel.
+    ///
+    /// Negative values are treated as `0.0`.
+    pub fn new(
+        (x, y): (f64, f64),
+        (cell_width, cell_height): (f64, f64),
+        device_pixel_ratio: f64,
+    ) -> Self {
+        let units = |px: f64| (px * CELL_OFFSET_UNITS_PER_PX).round() as u32;
+        Self {
+            x: units(x),
+            y: units(y),
+            device_x: units(x * device_pixel_ratio),
+            device_y: units(y * device_pixel_ratio),
+            cell_width: units(cell_width),
+            cell_height: units(cell_height),
+        }
+    }
+
+    /// Returns the offset in CSS pixels as `(x, y)`.
+    pub fn css_px(&self) -> (f64, f64) {
+        (px(self.x), px(self.y))
+    }
+
+    /// Returns the offset in device pixels as `(x, y)`.
+    pub fn device_px(&self) -> (f64, f64) {
+        (px(self.device_x), px(self.device_y))
+    }
+
+    /// Returns the size of the cell in CSS pixels as `(width, height)`.
+    pub fn cell_size_css_px(&self) -> (f64, f64) {
+        (px(self.cell_width), px(self.cell_height))
+    }
+
+    /// Returns the offset as a fraction of the cell size as `(x, y)`, with
+    /// each value in the range `0.0..1.0`.
+    pub fn fraction(&self) -> (f64, f64) {
+        let fraction = |offset: u32, size: u32| {
+            if size > 0 {
+                (offset as f64 / size as f64).clamp(0.0, 1.0 - f64::EPSILON)
+            } else {
+                0.0
+            }
+        };
+        (
+            fraction(self.x, self.cell_width),
+            fraction(self.y, self.cell_height),
+        )
+    }
+}
+
+/// Converts sub-pixel units of a [`CellOffset`] to pixels.
+fn px(units: u32) -> f64 {
+    units as f64 / CELL_OFFSET_UNITS_PER_PX
+}
+
+/// Convert a [`web_sys::KeyboardEvent`] to a [`KeyEvent`].
+impl From<web_sys::KeyboardEvent> for KeyEvent {
+    fn from(event: web_sys::KeyboardEvent) -> Self {
+        let ctrl = event.ctrl_key();
+        let alt = event.alt_key();
+        let shift = event.shift_key();
+        let meta = event.meta_key();
+        let physical_key = Some(event.code()).filter(|code| !code.is_empty());
+        let kind = if event.type_() == "keyup" {
+            KeyEventKind::Release
+        } else if event.repeat() {
+            KeyEventKind::Repeat
+        } else {
+            KeyEventKind::Press
+        };
+        KeyEvent {
+            code: event.into(),
+            physical_key,
+            ctrl,
+            alt,
+            shift,
+            meta,
+            kind,
+        }
+    }
+}
+
+/// A key code.
+///
+/// Keys on the numeric keypad are reported as the key they produce: digits,
+/// operators and the decimal separator as [`KeyCode::Char`], the keypad Enter
+/// as [`KeyCode::Enter`], and with num lock off, the navigation keys such as
+/// [`KeyCode::Home`] and [`KeyCode::Left`]. Use [`KeyEvent::is_keypad`] to
+/// tell them apart from the keys on the main keyboard.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum KeyCode {
+    /// Normal letter key input.
+    Char(char),
+    /// F keys.
+    F(u8),
+    /// Backspace key
+    Backspace,
+    /// Enter or return key
+    Enter,
+    /// Left arrow key
+    Left,
+    /// Right arrow key
+    Right,
+    /// Up arrow key
+    Up,
+    /// Down arrow key
+    Down,
+    /// Tab key
+    Tab,
+    /// Delete key
+    Delete,
+    /// Insert key
+    Insert,
+    /// Home key
+    Home,
+    /// End key
+    End,
+    /// Page up key
+    PageUp,
+    /// Page down key
+    PageDown,
+    /// Escape key
+    Esc,
+    /// Caps lock key
+    CapsLock,
+    /// Scroll lock key
+    ScrollLock,
+    /// Num lock key
+    NumLock,
+    /// Print screen key
+    PrintScreen,
+    /// Pause key
+    Pause,
+    /// Menu (context menu) key
+    Menu,
+    /// The "Begin" key, reported as `Clear` by browsers (keypad 5 with num lock off).
+    KeypadBegin,
+    /// A media key.
+    Media(MediaKeyCode),
+    /// A modifier key, pressed on its own.
+    Modifier(ModifierKeyCode),
+    /// Unidentified.
+    Unidentified,
+}
+
+/// A media key.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum MediaKeyCode {
+    /// Play media key
+    Play,
+    /// Pause media key
+    Pause,
+    /// Play/Pause media key
+    PlayPause,
+    /// Stop media key
+    Stop,
+    /// Fast-forward media key
+    FastForward,
+    /// Rewind media key
+    Rewind,
+    /// Next-track media key
+    TrackNext,
+    /// Previous-track media key
+    TrackPrevious,
+    /// Record media key
+    Record,
+    /// Lower-volume media key
+    LowerVolume,
+    /// Raise-volume media key
+    RaiseVolume,
+    /// Mute media key
+    MuteVolume,
+}
+
+/// A modifier key.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum ModifierKeyCode {
+    /// Left Shift key
+    LeftShift,
+    /// Left Control key
+    LeftControl,
+    /// Left Alt key
+    LeftAlt,
+    /// Left Super key (Command on macOS, Windows key elsewhere)
+    LeftSuper,
+    /// Left Hyper key
+    LeftHyper,
+    /// Right Shift key
+    RightShift,
+    /// Right Control key
+    RightControl,
+    /// Right Alt key
+    RightAlt,
+    /// Right Super key (Command on macOS, Windows key elsewhere)
+    RightSuper,
+    /// Right Hyper key
+    RightHyper,
+    /// Iso Level3 Shift key (AltGr)
+    IsoLevel3Shift,
+}
+
+/// Key names, as reported by [`web_sys::KeyboardEvent::key`], that map to a
+/// single [`KeyCode`].
+///
+/// Legacy names used by older browsers are included as aliases.
+///
+/// See <https://developer.mozilla.org/en-US/docs/Web/API/UI_Events/Keyboard_event_key_values>.
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+    ("Backspace", KeyCode::Backspace),
+    ("Enter", KeyCode::Enter),
+    ("ArrowLeft", KeyCode::Left),
+    ("Left", KeyCode::Left),
+    ("ArrowRight", KeyCode::Right),
+    ("Right", KeyCode::Right),
+    ("ArrowUp", KeyCode::Up),
+    ("Up", KeyCode::Up),
+    ("ArrowDown", KeyCode::Down),
+    ("Down", KeyCode::Down),
+    ("Tab", KeyCode::Tab),
+    ("Delete", KeyCode::Delete),
+    ("Del", KeyCode::Delete),
+    ("Insert", KeyCode::Insert),
+    ("Home", KeyCode::Home),
+    ("End", KeyCode::End),
+    ("PageUp", KeyCode::PageUp),
+    ("PageDown", KeyCode::PageDown),
+    ("Escape", KeyCode::Esc),
+    ("Esc", KeyCode::Esc),
+    ("Spacebar", KeyCode::Char(' ')),
+    ("CapsLock", KeyCode::CapsLock),
+    ("ScrollLock", KeyCode::ScrollLock),
+    ("Scroll", KeyCode::ScrollLock),
+    ("NumLock", KeyCode::NumLock),
+    ("PrintScreen", KeyCode::PrintScreen),
+    ("Pause", KeyCode::Pause),
+    ("ContextMenu", KeyCode::Menu),
+    ("Apps", KeyCode::Menu),
+    ("Clear", KeyCode::KeypadBegin),
+    ("MediaPlay", KeyCode::Media(MediaKeyCode::Play)),
+    ("MediaPause", KeyCode::Media(MediaKeyCode::Pause)),
+    ("MediaPlayPause", KeyCode::Media(MediaKeyCode::PlayPause)),
+    ("MediaStop", KeyCode::Media(MediaKeyCode::Stop)),
+    (
+        "MediaFastForward",
+        KeyCode::Media(MediaKeyCode::FastForward),
+    ),
+    ("MediaRewind", KeyCode::Media(MediaKeyCode::Rewind)),
+    ("MediaTrackNext", KeyCode::Media(MediaKeyCode::TrackNext)),
+    ("MediaNextTrack", KeyCode::Media(MediaKeyCode::TrackNext)),
+    (
+        "MediaTrackPrevious",
+        KeyCode::Media(MediaKeyCode::TrackPrevious),
+    ),
+    (
+        "MediaPreviousTrack",
+        KeyCode::Media(MediaKeyCode::TrackPrevious),
+    ),
+    ("MediaRecord", KeyCode::Media(MediaKeyCode::Record)),
+    ("AudioVolumeDown", KeyCode::Media(MediaKeyCode::LowerVolume)),
+    ("VolumeDown", KeyCode::Media(MediaKeyCode::LowerVolume)),
+    ("AudioVolumeUp", KeyCode::Media(MediaKeyCode::RaiseVolume)),
+    ("VolumeUp", KeyCode::Media(MediaKeyCode::RaiseVolume)),
+    ("AudioVolumeMute", KeyCode::Media(MediaKeyCode::MuteVolume)),
+    ("VolumeMute", KeyCode::Media(MediaKeyCode::MuteVolume)),
+    (
+        "AltGraph",
+        KeyCode::Modifier(ModifierKeyCode::IsoLevel3Shift),
+    ),
+];
+
+/// Modifier key names with their left and right [`ModifierKeyCode`]s.
+const MODIFIER_KEY_NAMES: &[(&str, ModifierKeyCode, ModifierKeyCode)] = &[
+    (
+        "Shift",
+        ModifierKeyCode::LeftShift,
+        ModifierKeyCode::RightShift,
+    ),
+    (
+        "Control",
+        ModifierKeyCode::LeftControl,
+        ModifierKeyCode::RightControl,
+    ),
+    ("Alt", ModifierKeyCode::LeftAlt, ModifierKeyCode::RightAlt),
+    (
+        "Meta",
+        ModifierKeyCode::LeftSuper,
+        ModifierKeyCode::RightSuper,
+    ),
+    (
+        "OS",
+        ModifierKeyCode::LeftSuper,
+        ModifierKeyCode::RightSuper,
+    ),
+    (
+        "Super",
+        ModifierKeyCode::LeftSuper,
+        ModifierKeyCode::RightSuper,
+    ),
+    (
+        "Hyper",
+        ModifierKeyCode::LeftHyper,
+        ModifierKeyCode::RightHyper,
+    ),
+];
+
+/// The highest function key number reported by browsers.
+const MAX_FUNCTION_KEY: u8 = 24;
+
+impl KeyCode {
+    /// Returns the [`KeyCode`] for a key name and location, as reported by
+    /// [`web_sys::KeyboardEvent::key`] and [`web_sys::KeyboardEvent::location`].
+    ///
+    /// The location is only used to tell left and right modifier keys apart.
+    fn from_key_name(key: &str, location: u32) -> Self {
+        let mut chars = key.chars();
+        if let (Some(char), None) = (chars.next(), chars.next()) {
+            return KeyCode::Char(char);
+        }
+
+        if let Some((_, code)) = KEY_NAMES.iter().find(|(name, _)| *name == key) {
+            return code.clone();
+        }
+
+        if let Some((_, left, right)) = MODIFIER_KEY_NAMES.iter().find(|(name, ..)| *name == key) {
+            return if location == web_sys::KeyboardEvent::DOM_KEY_LOCATION_RIGHT {
+                KeyCode::Modifier(*right)
+            } else {
+                KeyCode::Modifier(*left)
+            };
+        }
+
+        match key.strip_prefix('F').and_then(|n| n.parse::<u8>().ok()) {
+            Some(n @ 1..=MAX_FUNCTION_KEY) => KeyCode::F(n),
+            _ => KeyCode::Unidentified,
+        }
+    }
+}
+
+/// Convert a [`web_sys::KeyboardEvent`] to a [`KeyCode`].
+impl From<web_sys::KeyboardEvent> for KeyCode {
+    fn from(event: web_sys::KeyboardEvent) -> Self {
+        KeyCode::from_key_name(&event.key(), event.location())
+    }
+}
+
+/// A mouse button.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MouseButton {
+    /// Left mouse button
+    Left,
+    /// Right mouse button
+    Right,
+    /// Middle mouse button
+    Middle,
+    /// Back mouse button
+    Back,
+    /// Forward mouse button
+    Forward,
+    /// Unidentified mouse button
+    Unidentified,
+}
+
+/// The type of mouse event that occurred.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MouseEventKind {
+    /// Mouse cursor moved.
+    Moved,
+    /// Mouse button was pressed down.
+    ButtonDown(MouseButton),
+    /// Mouse button was released.
+    ButtonUp(MouseButton),
+    /// Mouse cursor moved while a button was held down.
+    ///
+    /// The pointer is captured while a button is held, so drags continue to
+    /// be reported when the cursor leaves the terminal, with the position
+    /// clamped to its edge.
+    Drag(MouseButton),
+    /// Mouse button was clicked (pressed and released).
+    SingleClick(MouseButton),
+    /// Mouse button was double-clicked.
+    DoubleClick(MouseButton),
+    /// Mouse button was triple-clicked.
+    ///
+    /// Reported after the [`MouseEventKind::SingleClick`] of the third click,
+    /// like [`MouseEventKind::DoubleClick`] is after the second.
+    TripleClick(MouseButton),
+    /// Mouse cursor entered the terminal area.
+    Entered,
+    /// Mouse cursor left the terminal area.
+    Exited,
+    /// Mouse wheel or trackpad was scrolled up.
+    ScrollUp,
+    /// Mouse wheel or trackpad was scrolled down.
+    ScrollDown,
+    /// Mouse wheel or trackpad was scrolled left.
+    ScrollLeft,
+    /// Mouse wheel or trackpad was scrolled right.
+    ScrollRight,
+    /// Unidentified mouse event.
+    Unidentified,
+}
+
+/// Convert a [`web_sys::MouseEvent`] to a [`MouseButton`].
+impl From<i16> for MouseButton {
+    fn from(button: i16) -> Self {
+        match button {
+            0 => MouseButton::Left,
+            1 => MouseButton::Middle,
+            2 => MouseButton::Right,
+            3 => MouseButton::Back,
+            4 => MouseButton::Forward,
+            _ => MouseButton::Unidentified,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEFT: u32 = web_sys::KeyboardEvent::DOM_KEY_LOCATION_LEFT;
+    const RIGHT: u32 = web_sys::KeyboardEvent::DOM_KEY_LOCATION_RIGHT;
+    const STANDARD: u32 = web_sys::KeyboardEvent::DOM_KEY_LOCATION_STANDARD;
+
+    /// Returns a key press event with the given modifiers.
+    pub(super) fn key_event(
+        code: KeyCode,
+        ctrl: bool,
+        alt: bool,
+        shift: bool,
+        meta: bool,
+    ) -> KeyEvent {
+        KeyEvent {
+            code,
+            physical_key: None,
+            ctrl,
+            alt,
+            shift,
+            meta,
+            kind: KeyEventKind::Press,
+        }
+    }
+
+    #[test]
+    fn test_key_names() {
+        for (name, code) in KEY_NAMES {
+            assert_eq!(&KeyCode::from_key_name(name, STANDARD), code, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_modifier_key_names() {
+        for (name, left, right) in MODIFIER_KEY_NAMES {
+            assert_eq!(
+                KeyCode::from_key_name(name, LEFT),
+                KeyCode::Modifier(*left),
+                "{name}"
+            );
+            assert_eq!(
+                KeyCode::from_key_name(name, RIGHT),
+                KeyCode::Modifier(*right),
+                "{name}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_char_keys() {
+        assert_eq!(KeyCode::from_key_name("a", STANDARD), KeyCode::Char('a'));
+        assert_eq!(KeyCode::from_key_name("A", STANDARD), KeyCode::Char('A'));
+        assert_eq!(KeyCode::from_key_name(" ", STANDARD), KeyCode::Char(' '));
+        assert_eq!(KeyCode::from_key_name("é", STANDARD), KeyCode::Char('é'));
+        assert_eq!(KeyCode::from_key_name("ß", STANDARD), KeyCode::Char('ß'));
+        // numpad digits are reported as characters
+        assert_eq!(
+            KeyCode::from_key_name("7", web_sys::KeyboardEvent::DOM_KEY_LOCATION_NUMPAD),
+            KeyCode::Char('7')
+        );
+    }
+
+    #[test]
+    fn test_keypad_keys() {
+        let key = |code, physical_key: &str| KeyEvent {
+            physical_key: Some(physical_key.to_string()),
+            ..key_event(code, false, false, false, false)
+        };
+        assert!(key(KeyCode::Char('7'), "Numpad7").is_keypad());
+        assert!(key(KeyCode::Enter, "NumpadEnter").is_keypad());
+        assert!(key(KeyCode::Home, "Numpad7").is_keypad());
+        assert!(!key(KeyCode::Char('7'), "Digit7").is_keypad());
+        assert!(!key(KeyCode::Enter, "Enter").is_keypad());
+        assert!(!key_event(KeyCode::Char('7'), false, false, false, false).is_keypad());
+    }
+
+    #[test]
+    fn test_function_keys() {
+        for n in 1..=MAX_FUNCTION_KEY {
+            assert_eq!(
+                KeyCode::from_key_name(&format!("F{n}"), STANDARD),
+                KeyCode::F(n)
+            );
+        }
+        assert_eq!(
+            KeyCode::from_key_name("F0", STANDARD),
+            KeyCode::Unidentified
+        );
+        assert_eq!(
+            KeyCode::from_key_name("F25", STANDARD),
+            KeyCode::Unidentified
+        );
+        assert_eq!(
+            KeyCode::from_key_name("Fn", STANDARD),
+            KeyCode::Unidentified
+        );
+    }
+
+    #[test]
+    fn test_unidentified_keys() {
+        assert_eq!(KeyCode::from_key_name("", STANDARD), KeyCode::Unidentified);
+        assert_eq!(
+            KeyCode::from_key_name("Dead", STANDARD),
+            KeyCode::Unidentified
+        );
+        assert_eq!(
+            KeyCode::from_key_name("Unidentified", STANDARD),
+            KeyCode::Unidentified
+        );
+    }
+
+    #[test]
+    fn test_mouse_event_position() {
+        let mut event = MouseEvent {
+            kind: MouseEventKind::Moved,
+            col: 3,
+            row: 5,
+            offset: None,
+            ctrl: false,
+            alt: false,
+            shift: false,
+            meta: false,
+        };
+        assert_eq!(event.position(), (3.0, 5.0));
+
+        let offset = CellOffset::new((2.5, 15.0), (10.0, 20.0), 2.0);
+        event.offset = Some(offset);
+        assert_eq!(offset.css_px(), (2.5, 15.0));
+        assert_eq!(offset.device_px(), (5.0, 30.0));
+        assert_eq!(offset.cell_size_css_px(), (10.0, 20.0));
+        assert_eq!(offset.fraction(), (0.25, 0.75));
+        assert_eq!(event.position(), (3.25, 5.75));
+    }
+
+    #[test]
+    fn test_into_event() {
+        assert_eq!(Event::from(FocusEvent::Gained), Event::FocusGained);
+        assert_eq!(Event::from(FocusEvent::Lost), Event::FocusLost);
+        let resize = ResizeEvent {
+            cols: 80,
+            rows: 24,
+            width: 800,
+            height: 480,
+        };
+        assert_eq!(Event::from(resize), Event::Resize(80, 24));
+        let key = key_event(KeyCode::Char('q'), true, false, false, false);
+        assert_eq!(Event::from(key.clone()), Event::Key(key));
+        assert_eq!(
+            Event::from(ImeEvent::Commit("漢字".into())),
+            Event::Ime(ImeEvent::Commit("漢字".into()))
+        );
+    }
+}
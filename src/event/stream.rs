@@ -0,0 +1,240 @@
+//! Buffered input events, consumed as a [`Stream`] or polled once per frame.
+
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll, Waker},
+};
+
+use futures_core::Stream;
+
+use crate::event::Event;
+
+/// A [`Stream`] of input [`Event`]s.
+///
+/// Taken from a terminal with [`WebRenderer::event_stream`], this allows
+/// handling input in a single async task, e.g. together with timers and
+/// `fetch` responses:
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use futures::StreamExt;
+/// use ratzilla::{event::Event, DomBackend, WebRenderer};
+/// use ratatui::Terminal;
+///
+/// let mut terminal = Terminal::new(DomBackend::new()?)?;
+/// let mut events = terminal.event_stream()?;
+///
+/// wasm_bindgen_futures::spawn_local(async move {
+///     while let Some(event) = events.next().await {
+///         if let Event::Key(key) = event {
+///             // handle the key
+///         }
+///     }
+/// });
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Events are buffered until they are polled. The stream ends once all of the
+/// event handlers it was set up with are replaced or cleared, or the terminal
+/// is dropped.
+///
+/// [`WebRenderer::event_stream`]: crate::WebRenderer::event_stream
+#[derive(Debug)]
+pub struct EventStream {
+    /// State shared with the [`EventSender`].
+    shared: Rc<RefCell<Shared>>,
+}
+
+/// State shared between an [`EventStream`] and its [`EventSender`].
+#[derive(Debug, Default)]
+struct Shared {
+    /// Events that have not been polled yet.
+    events: VecDeque<Event>,
+    /// The waker of the task polling the stream.
+    waker: Option<Waker>,
+    /// Whether the sender was dropped.
+    closed: bool,
+}
+
+/// The sending half of an [`EventStream`], owned by the event handlers.
+///
+/// The stream is closed when this is dropped.
+#[derive(Debug)]
+pub(crate) struct EventSender {
+    /// State shared with the [`EventStream`].
+    shared: Rc<RefCell<Shared>>,
+}
+
+impl EventStream {
+    /// Creates a new [`EventStream`] along with its sending half.
+    pub(crate) fn channel() -> (EventSender, Self) {
+        let shared = Rc::new(RefCell::new(Shared::default()));
+        (
+            EventSender {
+                shared: shared.clone(),
+            },
+            Self { shared },
+        )
+    }
+}
+
+impl EventStream {
+    /// Returns an iterator over the events buffered so far, without waiting
+    /// for more.
+    pub fn pending(&mut self) -> PendingEvents<'_> {
+        PendingEvents { stream: self }
+    }
+}
+
+/// An iterator over the buffered events of an [`EventStream`].
+///
+/// Created with [`EventStream::pending`], or handed to the render callback of
+/// [`WebRenderer::draw_web_with_events`]. Events that are not consumed stay
+/// buffered.
+///
+/// [`WebRenderer::draw_web_with_events`]: crate::WebRenderer::draw_web_with_events
+#[derive(Debug)]
+pub struct PendingEvents<'a> {
+    /// The stream to take the events from.
+    stream: &'a mut EventStream,
+}
+
+impl Iterator for PendingEvents<'_> {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.stream.shared.borrow_mut().events.pop_front()
+    }
+}
+
+impl EventSender {
+    /// Queues an event and wakes the task polling the stream.
+    pub fn send(&self, event: Event) {
+        let waker = {
+            let mut shared = self.shared.borrow_mut();
+            shared.events.push_back(event);
+            shared.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl Drop for EventSender {
+    fn drop(&mut self) {
+        let waker = {
+            let mut shared = self.shared.borrow_mut();
+            shared.closed = true;
+            shared.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Event;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut shared = self.shared.borrow_mut();
+        match shared.events.pop_front() {
+            Some(event) => Poll::Ready(Some(event)),
+            None if shared.closed => Poll::Ready(None),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let shared = self.shared.borrow();
+        let buffered = shared.events.len();
+        (buffered, if shared.closed { Some(buffered) } else { None })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::FocusEvent;
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        task::Wake,
+    };
+
+    /// A waker that records whether it was woken.
+    #[derive(Default)]
+    struct Flag(AtomicBool);
+
+    impl Wake for Flag {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_event_stream() {
+        let flag = Arc::new(Flag::default());
+        let waker = Waker::from(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let (sender, mut stream) = EventStream::channel();
+        assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Pending);
+
+        sender.send(FocusEvent::Gained.into());
+        sender.send(Event::Paste("rats".into()));
+        assert!(flag.0.swap(false, Ordering::SeqCst));
+        assert_eq!(stream.size_hint(), (2, None));
+        assert_eq!(
+            Pin::new(&mut stream).poll_next(&mut cx),
+            Poll::Ready(Some(Event::FocusGained))
+        );
+
+        // buffered events are still delivered after the sender is dropped
+        drop(sender);
+        assert_eq!(
+            Pin::new(&mut stream).poll_next(&mut cx),
+            Poll::Ready(Some(Event::Paste("rats".into())))
+        );
+        assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Ready(None));
+    }
+
+    #[test]
+    fn test_pending_events() {
+        let (sender, mut stream) = EventStream::channel();
+        assert_eq!(stream.pending().next(), None);
+
+        sender.send(FocusEvent::Gained.into());
+        sender.send(FocusEvent::Lost.into());
+        sender.send(Event::Resize(80, 24));
+        assert_eq!(stream.pending().next(), Some(Event::FocusGained));
+        assert_eq!(
+            stream.pending().collect::<Vec<_>>(),
+            vec![Event::FocusLost, Event::Resize(80, 24)]
+        );
+        assert_eq!(stream.pending().next(), None);
+    }
+
+    #[test]
+    fn test_event_stream_wakes_on_close() {
+        let flag = Arc::new(Flag::default());
+        let waker = Waker::from(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+
+        let (sender, mut stream) = EventStream::channel();
+        assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Pending);
+        drop(sender);
+        assert!(flag.0.load(Ordering::SeqCst));
+        assert_eq!(Pin::new(&mut stream).poll_next(&mut cx), Poll::Ready(None));
+    }
+}
@@ -0,0 +1,182 @@
+//! Conversions between ratzilla events and [`tui_textarea::Input`].
+//!
+//! These mirror the conversions tui-textarea provides for its own backends:
+//! keys and mouse events it doesn't handle are converted to [`Key::Null`],
+//! which a [`TextArea`](tui_textarea::TextArea) ignores.
+
+use tui_textarea::{Input, Key};
+
+use crate::{
+    error::Error,
+    event::{Event, KeyCode, KeyEvent, KeyEventKind, MouseEvent, MouseEventKind},
+};
+
+/// Convert a [`KeyEvent`] to an [`Input`].
+///
+/// Key releases are converted to the default [`Input`], so that a text area
+/// only reacts to key presses. The meta modifier is dropped.
+impl From<KeyEvent> for Input {
+    fn from(event: KeyEvent) -> Self {
+        if event.kind == KeyEventKind::Release {
+            return Self::default();
+        }
+        let key = match event.code {
+            KeyCode::Char(c) => Key::Char(c),
+            KeyCode::F(n) => Key::F(n),
+            KeyCode::Backspace => Key::Backspace,
+            KeyCode::Enter => Key::Enter,
+            KeyCode::Left => Key::Left,
+            KeyCode::Right => Key::Right,
+            KeyCode::Up => Key::Up,
+            KeyCode::Down => Key::Down,
+            KeyCode::Tab => Key::Tab,
+            KeyCode::Delete => Key::Delete,
+            KeyCode::Home => Key::Home,
+            KeyCode::End => Key::End,
+            KeyCode::PageUp => Key::PageUp,
+            KeyCode::PageDown => Key::PageDown,
+            KeyCode::Esc => Key::Esc,
+            _ => Key::Null,
+        };
+        Self {
+            key,
+            ctrl: event.ctrl,
+            alt: event.alt,
+            shift: event.shift,
+        }
+    }
+}
+
+/// Convert a [`MouseEvent`] to an [`Input`].
+///
+/// Only vertical scrolling is converted; other mouse events become
+/// [`Key::Null`].
+impl From<MouseEvent> for Input {
+    fn from(event: MouseEvent) -> Self {
+        let key = match event.kind {
+            MouseEventKind::ScrollDown => Key::MouseScrollDown,
+            MouseEventKind::ScrollUp => Key::MouseScrollUp,
+            _ => Key::Null,
+        };
+        Self {
+            key,
+            ctrl: event.ctrl,
+            alt: event.alt,
+            shift: event.shift,
+        }
+    }
+}
+
+/// Convert an [`Event`] to an [`Input`].
+///
+/// Events other than key and mouse events become [`Key::Null`].
+impl From<Event> for Input {
+    fn from(event: Event) -> Self {
+        match event {
+            Event::Key(key) => key.into(),
+            Event::Mouse(mouse) => mouse.into(),
+            _ => Self::default(),
+        }
+    }
+}
+
+/// Convert an [`Input`] to a [`KeyEvent`].
+///
+/// Fails for inputs that aren't keys, i.e. [`Key::Copy`], [`Key::Cut`],
+/// [`Key::Paste`], mouse scrolling and [`Key::Null`].
+impl TryFrom<Input> for KeyEvent {
+    type Error = Error;
+
+    fn try_from(input: Input) -> Result<Self, Self::Error> {
+        let code = match input.key {
+            Key::Char(c) => KeyCode::Char(c),
+            Key::F(n) => KeyCode::F(n),
+            Key::Backspace => KeyCode::Backspace,
+            Key::Enter => KeyCode::Enter,
+            Key::Left => KeyCode::Left,
+            Key::Right => KeyCode::Right,
+            Key::Up => KeyCode::Up,
+            Key::Down => KeyCode::Down,
+            Key::Tab => KeyCode::Tab,
+            Key::Delete => KeyCode::Delete,
+            Key::Home => KeyCode::Home,
+            Key::End => KeyCode::End,
+            Key::PageUp => KeyCode::PageUp,
+            Key::PageDown => KeyCode::PageDown,
+            Key::Esc => KeyCode::Esc,
+            _ => return Err(Error::UnsupportedEvent(format!("{input:?}"))),
+        };
+        Ok(KeyEvent {
+            code,
+            physical_key: None,
+            ctrl: input.ctrl,
+            alt: input.alt,
+            shift: input.shift,
+            meta: false,
+            kind: KeyEventKind::Press,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event::{tests::key_event, MouseButton};
+
+    #[test]
+    fn test_key_event_round_trip() {
+        let events = [
+            key_event(KeyCode::Char('a'), false, false, false, false),
+            key_event(KeyCode::Char('A'), true, false, true, false),
+            key_event(KeyCode::Enter, false, true, false, false),
+            key_event(KeyCode::Tab, false, false, true, false),
+            key_event(KeyCode::F(12), false, false, false, false),
+        ];
+        for event in events {
+            assert_eq!(
+                KeyEvent::try_from(Input::from(event.clone())).ok(),
+                Some(event)
+            );
+        }
+    }
+
+    #[test]
+    fn test_event_round_trip() {
+        let event = key_event(KeyCode::Backspace, true, false, false, false);
+        let input = Input::from(Event::Key(event.clone()));
+        assert_eq!(input.key, Key::Backspace);
+        assert_eq!(KeyEvent::try_from(input).ok(), Some(event));
+    }
+
+    #[test]
+    fn test_ignored_inputs() {
+        let release = KeyEvent {
+            kind: KeyEventKind::Release,
+            ..key_event(KeyCode::Char('a'), false, false, false, false)
+        };
+        assert_eq!(Input::from(release), Input::default());
+        assert_eq!(
+            Input::from(key_event(KeyCode::CapsLock, false, false, false, false)).key,
+            Key::Null
+        );
+        assert_eq!(Input::from(Event::FocusGained), Input::default());
+        assert!(KeyEvent::try_from(Input::default()).is_err());
+    }
+
+    #[test]
+    fn test_mouse_event() {
+        let mut event = MouseEvent {
+            kind: MouseEventKind::ScrollUp,
+            col: 3,
+            row: 4,
+            offset: None,
+            ctrl: false,
+            alt: false,
+            shift: false,
+            meta: false,
+        };
+        assert_eq!(Input::from(event.clone()).key, Key::MouseScrollUp);
+        event.kind = MouseEventKind::ButtonDown(MouseButton::Left);
+        assert_eq!(Input::from(event).key, Key::Null);
+    }
+}
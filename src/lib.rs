@@ -19,6 +19,9 @@ pub mod backend;
 /// Rendering.
 mod render;
 
+/// Render loop scheduling.
+mod render_loop;
+
 // Re-export ratatui crate.
 pub use ratatui;
 
@@ -30,6 +33,9 @@ pub use backend::{
     cell_sized::CellSized,
     cursor::CursorShape,
     dom::DomBackend,
+    key_capture::KeyCapture,
+    mouse_cursor::{MouseCursor, MouseCursors},
     webgl2::{FontAtlasConfig, SelectionMode, WebGl2Backend},
 };
 pub use render::{WebEventHandler, WebRenderer};
+pub use render_loop::{RedrawHandle, RenderErrorAction, RenderLoop, RenderOptions};
@@ -1,10 +1,19 @@
-use ratatui::{prelude::Backend, Frame, Terminal};
-use std::{cell::RefCell, rc::Rc};
-use web_sys::{wasm_bindgen::prelude::*, window};
+use ratatui::{
+    prelude::Backend,
+    style::Color,
+    widgets::{Paragraph, Wrap},
+    Frame, Terminal,
+};
+use std::{cell::RefCell, collections::VecDeque, io, rc::Rc};
+use web_sys::{wasm_bindgen::prelude::*, window, Element};
 
 use crate::{
     error::Error,
-    event::{KeyEvent, MouseEvent},
+    event::{
+        Event, EventStream, FocusEvent, GamepadEvent, GestureEvent, ImeEvent, KeyEvent, MouseEvent,
+        PendingEvents, ResizeEvent,
+    },
+    render_loop::{InputTargets, RenderErrorAction, RenderLoop, RenderOptions},
 };
 
 /// Trait for rendering on the web.
@@ -17,14 +26,171 @@ pub trait WebRenderer {
     /// This method takes a closure that will be called on every update
     /// that the browser makes during [`requestAnimationFrame`] calls.
     ///
+    /// The render loop keeps running for the lifetime of the page. Use
+    /// [`start_web`] to get a [`RenderLoop`] handle to control it instead.
+    ///
+    /// If the backend fails to draw a frame, the error is shown as with
+    /// [`RenderErrorAction::Show`]. Use [`try_draw_web`] to handle errors.
+    ///
+    /// [`start_web`]: WebRenderer::start_web
+    /// [`try_draw_web`]: WebRenderer::try_draw_web
     /// [`requestAnimationFrame`]: https://developer.mozilla.org/en-US/docs/Web/API/Window/requestAnimationFrame
     fn draw_web<F>(self, render_callback: F)
     where
-        F: FnMut(&mut Frame) + 'static;
+        Self: Sized + 'static,
+        F: FnMut(&mut Frame) + 'static,
+    {
+        self.draw_web_with_options(RenderOptions::default(), render_callback)
+            .detach();
+    }
+
+    /// Renders the terminal on the web, and returns a [`RenderLoop`] handle
+    /// to pause, resume or stop the render loop.
+    ///
+    /// This is like [`draw_web`], but the loop stops when the handle is
+    /// dropped. Call [`RenderLoop::detach`] to keep it running instead.
+    ///
+    /// [`draw_web`]: WebRenderer::draw_web
+    fn start_web<F>(self, render_callback: F) -> RenderLoop<Self>
+    where
+        Self: Sized,
+        F: FnMut(&mut Frame) + 'static,
+    {
+        self.draw_web_with_options(RenderOptions::default(), render_callback)
+    }
+
+    /// Renders the terminal on the web, with the given [`RenderOptions`].
+    ///
+    /// This is like [`start_web`], but e.g. allows only drawing frames when
+    /// something changed, with [`RenderOptions::on_demand`].
+    ///
+    /// [`start_web`]: WebRenderer::start_web
+    fn draw_web_with_options<F>(
+        self,
+        options: RenderOptions,
+        mut render_callback: F,
+    ) -> RenderLoop<Self>
+    where
+        Self: Sized,
+        F: FnMut(&mut Frame) + 'static,
+    {
+        self.try_draw_web_with_options(
+            options,
+            move |frame| {
+                render_callback(frame);
+                Ok::<(), io::Error>(())
+            },
+            |_| RenderErrorAction::Show,
+        )
+    }
+
+    /// Renders the terminal on the web, with a render callback that can fail.
+    ///
+    /// This is like [`start_web`], but errors returned by the render callback
+    /// or the backend are passed to `on_error`, which decides whether to
+    /// retry drawing the frame, stop the render loop, or show the error.
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use ratzilla::{ratatui::Terminal, RenderErrorAction, WebGl2Backend, WebRenderer};
+    ///
+    /// let terminal = Terminal::new(WebGl2Backend::new()?)?;
+    /// terminal
+    ///     .try_draw_web(
+    ///         |frame| {
+    ///             // render the frame...
+    ///             Ok::<(), std::io::Error>(())
+    ///         },
+    ///         |error| match error.kind() {
+    ///             std::io::ErrorKind::WouldBlock => RenderErrorAction::Retry,
+    ///             _ => RenderErrorAction::Show,
+    ///         },
+    ///     )
+    ///     .detach();
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// [`start_web`]: WebRenderer::start_web
+    fn try_draw_web<F, E, H>(self, render_callback: F, on_error: H) -> RenderLoop<Self>
+    where
+        Self: Sized,
+        F: FnMut(&mut Frame) -> Result<(), E> + 'static,
+        E: Into<io::Error>,
+        H: FnMut(&io::Error) -> RenderErrorAction + 'static,
+    {
+        self.try_draw_web_with_options(RenderOptions::default(), render_callback, on_error)
+    }
+
+    /// Renders the terminal on the web with a render callback that can fail,
+    /// and the given [`RenderOptions`].
+    ///
+    /// See [`try_draw_web`] and [`draw_web_with_options`].
+    ///
+    /// [`try_draw_web`]: WebRenderer::try_draw_web
+    /// [`draw_web_with_options`]: WebRenderer::draw_web_with_options
+    fn try_draw_web_with_options<F, E, H>(
+        self,
+        options: RenderOptions,
+        render_callback: F,
+        on_error: H,
+    ) -> RenderLoop<Self>
+    where
+        Self: Sized,
+        F: FnMut(&mut Frame) -> Result<(), E> + 'static,
+        E: Into<io::Error>,
+        H: FnMut(&io::Error) -> RenderErrorAction + 'static;
+
+    /// Renders the terminal on the web, handing the input events received
+    /// since the previous frame to the render callback.
+    ///
+    /// This is an alternative to registering event callbacks, which fire
+    /// outside of the render loop: events are buffered and can be handled
+    /// synchronously at the start of each frame, like a crossterm
+    /// `event::poll`/`read` loop. It sets up the same handlers as
+    /// [`on_event`], which it replaces.
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use ratzilla::{event::{Event, KeyCode}, DomBackend, WebRenderer};
+    /// use ratatui::{widgets::Paragraph, Terminal};
+    ///
+    /// let terminal = Terminal::new(DomBackend::new()?)?;
+    /// let mut counter = 0;
+    /// terminal.draw_web_with_events(move |frame, events| {
+    ///     for event in events {
+    ///         if let Event::Key(key) = event {
+    ///             if key.code == KeyCode::Char(' ') {
+    ///                 counter += 1;
+    ///             }
+    ///         }
+    ///     }
+    ///     frame.render_widget(Paragraph::new(counter.to_string()), frame.area());
+    /// })?
+    /// .detach();
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if event listener attachment fails.
+    ///
+    /// [`on_event`]: WebRenderer::on_event
+    fn draw_web_with_events<F>(mut self, mut render_callback: F) -> Result<RenderLoop<Self>, Error>
+    where
+        Self: Sized,
+        F: FnMut(&mut Frame, PendingEvents<'_>) + 'static,
+    {
+        let mut events = self.event_stream()?;
+        Ok(self.start_web(move |frame| render_callback(frame, events.pending())))
+    }
 
     /// Handles key events.
     ///
-    /// This method takes a closure that will be called on every `keydown` event.
+    /// This method takes a closure that will be called on every `keydown` event,
+    /// and on every `keyup` event if the backend is configured to report key
+    /// releases.
     ///
     /// # Errors
     ///
@@ -38,7 +204,8 @@ pub trait WebRenderer {
     ///
     /// This method takes a closure that will be called on mouse events.
     /// The callback receives [`MouseEvent`]s with terminal grid coordinates
-    /// (`col`, `row`) instead of raw pixel coordinates.
+    /// (`col`, `row`) instead of raw pixel coordinates. Touch input is mapped
+    /// to mouse events as well.
     ///
     /// # Errors
     ///
@@ -47,12 +214,126 @@ pub trait WebRenderer {
     where
         F: FnMut(MouseEvent) + 'static;
 
+    /// Handles touch gestures.
+    ///
+    /// This method takes a closure that will be called when the user swipes
+    /// or pinches on a touch screen.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if event listener attachment fails.
+    fn on_gesture_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(GestureEvent) + 'static;
+
+    /// Handles paste events.
+    ///
+    /// This method takes a closure that will be called with the pasted text
+    /// whenever the user pastes into the terminal, similar to bracketed paste
+    /// in native terminals. Multi-line pastes arrive as a single call.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if event listener attachment fails.
+    fn on_paste_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(String) + 'static;
+
+    /// Handles focus events.
+    ///
+    /// This method takes a closure that will be called whenever the terminal
+    /// gains or loses keyboard focus, e.g. when the user clicks outside of it
+    /// or switches to another tab.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if event listener attachment fails.
+    fn on_focus_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(FocusEvent) + 'static;
+
+    /// Handles resize events.
+    ///
+    /// This method takes a closure that will be called with the new grid and
+    /// pixel sizes whenever the terminal is resized, before the next frame is
+    /// drawn.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the handler can't be set up.
+    fn on_resize<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(ResizeEvent) + 'static;
+
+    /// Handles IME composition events.
+    ///
+    /// This method takes a closure that will be called while the user composes
+    /// text with an input method, e.g. for Chinese, Japanese or Korean input.
+    /// The candidate window is placed at the cursor position set with
+    /// [`Frame::set_cursor_position`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if event listener attachment fails.
+    fn on_ime_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(ImeEvent) + 'static;
+
+    /// Handles gamepad events.
+    ///
+    /// This method takes a closure that will be called when a gamepad is
+    /// connected or disconnected, or one of its buttons or axes changes.
+    /// Gamepads are polled on every animation frame of the loop started by
+    /// [`draw_web`], even while it is paused or draws on demand.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the handler can't be set up.
+    ///
+    /// [`draw_web`]: WebRenderer::draw_web
+    fn on_gamepad_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(GamepadEvent) + 'static;
+
+    /// Handles all input events with a single callback.
+    ///
+    /// This method takes a closure that will be called with every key, mouse,
+    /// paste, focus, resize, IME and gamepad event, in the order they occur.
+    /// Gesture events are included if the backend was set up to report them.
+    /// It replaces any handlers set with the other `on_*` methods.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if event listener attachment fails.
+    fn on_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(Event) + 'static;
+
+    /// Returns a [`Stream`] of all input events.
+    ///
+    /// This sets up the same handlers as [`on_event`], which it replaces, and
+    /// buffers the events until they are polled. See [`EventStream`] for an
+    /// example.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if event listener attachment fails.
+    ///
+    /// [`Stream`]: futures_core::Stream
+    /// [`on_event`]: WebRenderer::on_event
+    fn event_stream(&mut self) -> Result<EventStream, Error> {
+        let (sender, stream) = EventStream::channel();
+        self.on_event(move |event| sender.send(event))?;
+        Ok(stream)
+    }
+
     /// Requests an animation frame.
+    ///
+    /// The request is ignored if the window is not available.
     fn request_animation_frame(f: &Closure<dyn FnMut()>) {
-        window()
-            .unwrap()
-            .request_animation_frame(f.as_ref().unchecked_ref())
-            .unwrap();
+        if let Some(window) = window() {
+            let _ = window.request_animation_frame(f.as_ref().unchecked_ref());
+        }
     }
 }
 
@@ -62,24 +343,41 @@ pub trait WebRenderer {
 /// [`WebEventHandler`] implementation.
 impl<T> WebRenderer for Terminal<T>
 where
-    T: Backend + WebEventHandler + 'static,
+    T: Backend<Error = io::Error> + WebEventHandler + 'static,
 {
-    fn draw_web<F>(mut self, mut render_callback: F)
+    fn try_draw_web_with_options<F, E, H>(
+        self,
+        options: RenderOptions,
+        mut render_callback: F,
+        mut on_error: H,
+    ) -> RenderLoop<Self>
     where
-        F: FnMut(&mut Frame) + 'static,
+        F: FnMut(&mut Frame) -> Result<(), E> + 'static,
+        E: Into<io::Error>,
+        H: FnMut(&io::Error) -> RenderErrorAction + 'static,
     {
-        let callback = Rc::new(RefCell::new(None));
-        *callback.borrow_mut() = Some(Closure::wrap(Box::new({
-            let cb = callback.clone();
-            move || {
-                self.draw(|frame| {
-                    render_callback(frame);
-                })
-                .unwrap();
-                Self::request_animation_frame(cb.borrow().as_ref().unwrap());
+        let input = |terminal: &Self| {
+            let backend = terminal.backend();
+            InputTargets {
+                element: backend.terminal_element(),
+                ime_input: backend.ime_input_element(),
+                document_key_events: backend.handles_document_key_events(),
             }
-        }) as Box<dyn FnMut()>));
-        Self::request_animation_frame(callback.borrow().as_ref().unwrap());
+        };
+        let poll = |terminal: &mut Self| terminal.backend_mut().poll_gamepads();
+        RenderLoop::start(options, self, input, poll, move |terminal, clear| {
+            let result = if clear { terminal.clear() } else { Ok(()) };
+            let Err(error) =
+                result.and_then(|()| terminal.try_draw(&mut render_callback).map(drop))
+            else {
+                return Ok(());
+            };
+            let action = on_error(&error);
+            if action == RenderErrorAction::Show {
+                show_render_error(terminal, &error);
+            }
+            Err(action)
+        })
     }
 
     fn on_key_event<F>(&mut self, callback: F) -> Result<(), Error>
@@ -95,6 +393,55 @@ where
     {
         self.backend_mut().on_mouse_event(callback)
     }
+
+    fn on_gesture_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(GestureEvent) + 'static,
+    {
+        self.backend_mut().on_gesture_event(callback)
+    }
+
+    fn on_paste_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(String) + 'static,
+    {
+        self.backend_mut().on_paste_event(callback)
+    }
+
+    fn on_focus_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(FocusEvent) + 'static,
+    {
+        self.backend_mut().on_focus_event(callback)
+    }
+
+    fn on_resize<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(ResizeEvent) + 'static,
+    {
+        self.backend_mut().on_resize(callback)
+    }
+
+    fn on_ime_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(ImeEvent) + 'static,
+    {
+        self.backend_mut().on_ime_event(callback)
+    }
+
+    fn on_gamepad_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(GamepadEvent) + 'static,
+    {
+        self.backend_mut().on_gamepad_event(callback)
+    }
+
+    fn on_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(Event) + 'static,
+    {
+        self.backend_mut().on_event(callback)
+    }
 }
 
 /// Backend-specific event handling with lifecycle management.
@@ -105,6 +452,10 @@ where
 /// - Automatic cleanup of event listeners when replaced or dropped
 /// - Extended mouse event support (enter/leave, click/dblclick)
 ///
+/// Only the mouse and key event handlers have to be implemented. The other
+/// handlers do nothing by default, so backends only implement the events
+/// they support.
+///
 /// # Example
 ///
 /// ```no_run
@@ -129,12 +480,20 @@ pub trait WebEventHandler {
     /// (`col`, `row`) instead of raw pixel coordinates. Coordinates are
     /// relative to the terminal element, not the viewport.
     ///
+    /// Touch input is mapped to mouse events: a tap is a left click, dragging
+    /// a finger presses, moves and releases the left button, and a long press
+    /// is a right click. Dragging a finger only works if the backend is set to
+    /// prevent touches from panning and zooming the page, e.g. with
+    /// [`DomBackendOptions::prevent_default_touch`].
+    ///
     /// Calling this method again will automatically clean up the previous
     /// event listeners before setting up new ones.
     ///
     /// # Errors
     ///
     /// Returns an error if event listener attachment fails.
+    ///
+    /// [`DomBackendOptions::prevent_default_touch`]: crate::backend::dom::DomBackendOptions::prevent_default_touch
     fn on_mouse_event<F>(&mut self, callback: F) -> Result<(), Error>
     where
         F: FnMut(MouseEvent) + 'static;
@@ -145,9 +504,41 @@ pub trait WebEventHandler {
     /// called manually to stop receiving mouse events.
     fn clear_mouse_events(&mut self);
 
+    /// Sets up touch gesture handlers.
+    ///
+    /// The callback receives a [`GestureEvent::Swipe`] when a single finger
+    /// quickly swipes across the terminal, and [`GestureEvent::Pinch`]
+    /// events while two fingers move towards or away from each other.
+    /// Gestures are recognized independently of the mouse events touch input
+    /// is mapped to by [`on_mouse_event`]. Touches on the terminal no longer
+    /// pan or zoom the page once a gesture handler is set.
+    ///
+    /// Calling this method again will automatically clean up the previous
+    /// event listeners before setting up new ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if event listener attachment fails.
+    ///
+    /// [`on_mouse_event`]: WebEventHandler::on_mouse_event
+    fn on_gesture_event<F>(&mut self, _callback: F) -> Result<(), Error>
+    where
+        F: FnMut(GestureEvent) + 'static,
+    {
+        Ok(())
+    }
+
+    /// Removes all gesture event handlers.
+    ///
+    /// This is automatically called when new handlers are set up, but can be
+    /// called manually to stop receiving gesture events.
+    fn clear_gesture_events(&mut self) {}
+
     /// Sets up keyboard event handlers.
     ///
-    /// The callback receives [`KeyEvent`]s for `keydown` events.
+    /// The callback receives [`KeyEvent`]s for `keydown` events, with
+    /// [`KeyEventKind::Repeat`] for auto-repeated keys. `keyup` events are
+    /// delivered as [`KeyEventKind::Release`] if enabled in the backend options.
     ///
     /// Calling this method again will automatically clean up the previous
     /// event listeners before setting up new ones.
@@ -162,6 +553,8 @@ pub trait WebEventHandler {
     /// Returns an error if event listener attachment fails.
     ///
     /// [`WebGl2Backend`]: crate::WebGl2Backend
+    /// [`KeyEventKind::Repeat`]: crate::event::KeyEventKind::Repeat
+    /// [`KeyEventKind::Release`]: crate::event::KeyEventKind::Release
     fn on_key_event<F>(&mut self, callback: F) -> Result<(), Error>
     where
         F: FnMut(KeyEvent) + 'static;
@@ -171,4 +564,309 @@ pub trait WebEventHandler {
     /// This is automatically called when new handlers are set up, but can be
     /// called manually to stop receiving key events.
     fn clear_key_events(&mut self);
+
+    /// Sets up paste event handlers.
+    ///
+    /// The callback receives the plain text of `paste` events on the terminal
+    /// element, with line endings normalized to `\n`. The text is not replayed
+    /// as key events.
+    ///
+    /// Calling this method again will automatically clean up the previous
+    /// event listeners before setting up new ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if event listener attachment fails.
+    fn on_paste_event<F>(&mut self, _callback: F) -> Result<(), Error>
+    where
+        F: FnMut(String) + 'static,
+    {
+        Ok(())
+    }
+
+    /// Removes all paste event handlers.
+    ///
+    /// This is automatically called when new handlers are set up, but can be
+    /// called manually to stop receiving paste events.
+    fn clear_paste_events(&mut self) {}
+
+    /// Sets up focus event handlers.
+    ///
+    /// The callback receives a [`FocusEvent`] whenever the terminal element
+    /// gains or loses keyboard focus.
+    ///
+    /// Calling this method again will automatically clean up the previous
+    /// event listeners before setting up new ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if event listener attachment fails.
+    fn on_focus_event<F>(&mut self, _callback: F) -> Result<(), Error>
+    where
+        F: FnMut(FocusEvent) + 'static,
+    {
+        Ok(())
+    }
+
+    /// Removes all focus event handlers.
+    ///
+    /// This is automatically called when new handlers are set up, but can be
+    /// called manually to stop receiving focus events.
+    fn clear_focus_events(&mut self) {}
+
+    /// Gives keyboard focus to the terminal element.
+    ///
+    /// The element is made focusable if it isn't already.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the terminal element can't be focused, or
+    /// [`Error::Unsupported`] by default.
+    fn focus(&self) -> Result<(), Error> {
+        Err(Error::Unsupported("focus"))
+    }
+
+    /// Removes keyboard focus from the terminal element, if it has focus.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the document can't be retrieved, or
+    /// [`Error::Unsupported`] by default.
+    fn blur(&self) -> Result<(), Error> {
+        Err(Error::Unsupported("blur"))
+    }
+
+    /// Returns whether the terminal element has keyboard focus.
+    ///
+    /// Returns `false` by default.
+    fn has_focus(&self) -> bool {
+        false
+    }
+
+    /// Sets up a resize handler.
+    ///
+    /// The callback receives a [`ResizeEvent`] with the new grid size and
+    /// pixel size (in CSS pixels) whenever the terminal is resized. It is
+    /// called once the backend has applied the new size, so that the next
+    /// frame is the first one drawn with it.
+    ///
+    /// Calling this method again replaces the previous handler.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the handler can't be set up.
+    fn on_resize<F>(&mut self, _callback: F) -> Result<(), Error>
+    where
+        F: FnMut(ResizeEvent) + 'static,
+    {
+        Ok(())
+    }
+
+    /// Removes the resize handler.
+    fn clear_resize_events(&mut self) {}
+
+    /// Sets up IME composition event handlers.
+    ///
+    /// The callback receives [`ImeEvent`]s while the user composes text with
+    /// an input method. Composition only works in editable elements, so a
+    /// hidden `<textarea>` is placed at the terminal cursor position and
+    /// takes keyboard focus from the terminal element. Other key events are
+    /// still delivered to the [`on_key_event`] handler.
+    ///
+    /// Calling this method again will automatically clean up the previous
+    /// event listeners before setting up new ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if event listener attachment fails.
+    ///
+    /// [`on_key_event`]: WebEventHandler::on_key_event
+    fn on_ime_event<F>(&mut self, _callback: F) -> Result<(), Error>
+    where
+        F: FnMut(ImeEvent) + 'static,
+    {
+        Ok(())
+    }
+
+    /// Removes all IME composition event handlers, along with the hidden
+    /// `<textarea>`.
+    ///
+    /// This is automatically called when new handlers are set up, but can be
+    /// called manually to stop receiving IME events.
+    fn clear_ime_events(&mut self) {}
+
+    /// Sets up gamepad event handlers.
+    ///
+    /// The callback receives a [`GamepadEvent`] when a gamepad is connected
+    /// or disconnected, or one of its buttons is pressed or released or one
+    /// of its axes moves. The browser has no events for these, so gamepads
+    /// are polled by the render loop on every animation frame while a handler
+    /// is set, see [`poll_gamepads`].
+    ///
+    /// Calling this method again replaces the previous handler.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the handler can't be set up.
+    ///
+    /// [`poll_gamepads`]: WebEventHandler::poll_gamepads
+    fn on_gamepad_event<F>(&mut self, _callback: F) -> Result<(), Error>
+    where
+        F: FnMut(GamepadEvent) + 'static,
+    {
+        Ok(())
+    }
+
+    /// Removes the gamepad event handler.
+    fn clear_gamepad_events(&mut self) {}
+
+    /// Polls the connected gamepads, and calls the gamepad event handler for
+    /// every change since the last poll.
+    ///
+    /// The render loop calls this on every animation frame, including while
+    /// no frames are drawn, e.g. in [on-demand] mode. Returns whether any
+    /// events were reported, or `None` if no gamepad handler is set, which
+    /// is also the default.
+    ///
+    /// [on-demand]: crate::RenderOptions::on_demand
+    fn poll_gamepads(&mut self) -> Option<bool> {
+        None
+    }
+
+    /// Sets up handlers for all input events, delivered to a single callback.
+    ///
+    /// The callback receives every key, mouse, paste, focus, resize, IME and
+    /// gamepad event as an [`Event`], in the order they occur. This replaces
+    /// any handlers set with the other `on_*` methods.
+    ///
+    /// Gesture events are only reported if [`handles_gesture_events`] returns
+    /// `true`, as recognizing them stops the browser from panning and zooming
+    /// the page on touches.
+    ///
+    /// Events that occur while the callback is running, e.g. a focus event
+    /// caused by focusing the terminal, are delivered once it returns.
+    ///
+    /// Calling this method again will automatically clean up the previous
+    /// event listeners before setting up new ones.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if event listener attachment fails.
+    ///
+    /// [`handles_gesture_events`]: WebEventHandler::handles_gesture_events
+    fn on_event<F>(&mut self, callback: F) -> Result<(), Error>
+    where
+        F: FnMut(Event) + 'static,
+    {
+        let callback = Rc::new(RefCell::new(callback));
+        let queue = Rc::new(RefCell::new(VecDeque::new()));
+        let forward = |callback: &Rc<RefCell<F>>| {
+            let callback = callback.clone();
+            let queue = queue.clone();
+            move |event: Event| {
+                queue.borrow_mut().push_back(event);
+                // If the callback is already running, it delivers the event
+                // once it returns
+                let Ok(mut callback) = callback.try_borrow_mut() else {
+                    return;
+                };
+                let next = || queue.borrow_mut().pop_front();
+                while let Some(event) = next() {
+                    callback(event);
+                }
+            }
+        };
+
+        let on_key = forward(&callback);
+        self.on_key_event(move |event| on_key(event.into()))?;
+        let on_mouse = forward(&callback);
+        self.on_mouse_event(move |event| on_mouse(event.into()))?;
+        let on_paste = forward(&callback);
+        self.on_paste_event(move |text| on_paste(Event::Paste(text)))?;
+        let on_focus = forward(&callback);
+        self.on_focus_event(move |event| on_focus(event.into()))?;
+        let on_resize = forward(&callback);
+        self.on_resize(move |event| on_resize(event.into()))?;
+        let on_ime = forward(&callback);
+        self.on_ime_event(move |event| on_ime(event.into()))?;
+        if self.handles_gesture_events() {
+            let on_gesture = forward(&callback);
+            self.on_gesture_event(move |event| on_gesture(event.into()))?;
+        }
+        let on_gamepad = forward(&callback);
+        self.on_gamepad_event(move |event| on_gamepad(event.into()))?;
+
+        Ok(())
+    }
+
+    /// Removes all event handlers.
+    fn clear_events(&mut self) {
+        self.clear_key_events();
+        self.clear_mouse_events();
+        self.clear_paste_events();
+        self.clear_focus_events();
+        self.clear_resize_events();
+        self.clear_ime_events();
+        self.clear_gesture_events();
+        self.clear_gamepad_events();
+    }
+
+    /// Returns the element the terminal is drawn to, if any.
+    ///
+    /// The render loop is suspended while this element is scrolled out of
+    /// view. In [on-demand] mode, it draws a frame after input events on
+    /// this element. Returns `None` by default.
+    ///
+    /// [on-demand]: crate::RenderOptions::on_demand
+    fn terminal_element(&self) -> Option<Element> {
+        None
+    }
+
+    /// Returns the hidden element that receives IME composition, if any.
+    ///
+    /// In [on-demand] mode, the render loop draws a frame after composition
+    /// and focus events on this element. Returns `None` by default.
+    ///
+    /// [on-demand]: crate::RenderOptions::on_demand
+    fn ime_input_element(&self) -> Option<Element> {
+        None
+    }
+
+    /// Returns whether key events are listened for on the whole document,
+    /// rather than on the terminal element.
+    ///
+    /// In [on-demand] mode, the render loop then draws a frame after any key
+    /// event on the page. Returns `false` by default.
+    ///
+    /// [on-demand]: crate::RenderOptions::on_demand
+    fn handles_document_key_events(&self) -> bool {
+        false
+    }
+
+    /// Returns whether touch gestures are reported to the [`on_event`]
+    /// handler.
+    ///
+    /// Returns `false` by default.
+    ///
+    /// [`on_event`]: WebEventHandler::on_event
+    fn handles_gesture_events(&self) -> bool {
+        false
+    }
+}
+
+/// Logs a render error to the browser console, and draws it in place of the
+/// terminal's content.
+///
+/// If the error can't be drawn either, that failure is logged as well.
+fn show_render_error<T: Backend<Error = io::Error>>(terminal: &mut Terminal<T>, error: &io::Error) {
+    web_sys::console::error_1(&format!("Render error: {error}").into());
+    let result = terminal.draw(|frame| {
+        let message = Paragraph::new(format!("Render error: {error}"))
+            .style(Color::Red)
+            .wrap(Wrap { trim: true });
+        frame.render_widget(message, frame.area());
+    });
+    if let Err(error) = result {
+        web_sys::console::error_1(&format!("Failed to show the render error: {error}").into());
+    }
 }
@@ -0,0 +1,897 @@
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+    time::Duration,
+};
+
+use web_sys::{
+    wasm_bindgen::{prelude::*, JsCast},
+    Element, EventTarget,
+};
+
+use crate::backend::event_callback::{EventCallback, IntersectionCallback};
+
+/// The event types on the terminal element that make the render loop draw a
+/// frame in on-demand mode.
+///
+/// Key and paste events on the hidden IME input are forwarded to the terminal
+/// element, so they are seen here too.
+const TERMINAL_EVENT_TYPES: &[&str] = &[
+    "keydown",
+    "keyup",
+    "pointerdown",
+    "pointerup",
+    "pointermove",
+    "pointerout",
+    "wheel",
+    "paste",
+    "focusin",
+    "focusout",
+    "compositionupdate",
+    "compositionend",
+];
+
+/// The event types on the hidden IME input that make the render loop draw a
+/// frame in on-demand mode.
+const IME_EVENT_TYPES: &[&str] = &["focusin", "focusout", "compositionupdate", "compositionend"];
+
+/// The event types on the document that make the render loop draw a frame in
+/// on-demand mode, if the backend listens for key events on the document.
+const DOCUMENT_EVENT_TYPES: &[&str] = &["keydown", "keyup"];
+
+/// The event types on the window that make the render loop draw a frame in
+/// on-demand mode.
+const WINDOW_EVENT_TYPES: &[&str] = &["resize"];
+
+/// The event types that report the page being hidden or shown.
+const VISIBILITY_EVENT_TYPES: &[&str] = &["visibilitychange"];
+
+/// How much earlier than the frame interval, in milliseconds, a frame may be
+/// drawn when the frame rate is capped, to allow for jitter in the animation
+/// frame timestamps.
+const FRAME_TIME_TOLERANCE: f64 = 1.0;
+
+/// The maximum time, in milliseconds, that fixed-timestep updates catch up on
+/// in a single frame, e.g. after the tab was in the background.
+const MAX_FIXED_UPDATE_TIME: f64 = 250.0;
+
+/// A handle for requesting that the terminal is redrawn.
+///
+/// In [on-demand](RenderOptions::on_demand) mode, frames are only drawn after
+/// input events, resizes, and calls to [`RedrawHandle::request_redraw`]. The
+/// handle can be cloned into async tasks, so that e.g. the completion of a
+/// `fetch` request is drawn:
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use ratzilla::{ratatui::Terminal, DomBackend, RenderOptions, WebRenderer};
+///
+/// let terminal = Terminal::new(DomBackend::new()?)?;
+/// let options = RenderOptions::new().on_demand(true);
+/// let redraw = options.redraw_handle();
+///
+/// wasm_bindgen_futures::spawn_local(async move {
+///     // fetch some data and store it...
+///     redraw.request_redraw();
+/// });
+///
+/// terminal
+///     .draw_web_with_options(options, |frame| {
+///         // render the data...
+///     })
+///     .detach();
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Requests made while a frame is being drawn are drawn in the next frame.
+/// In continuous mode, requests have no effect, as every frame is drawn.
+#[derive(Debug, Clone, Default)]
+pub struct RedrawHandle {
+    /// The state shared with the render loop.
+    inner: Rc<RedrawState>,
+}
+
+/// The state shared between a [`RedrawHandle`] and the render loop.
+#[derive(Default)]
+struct RedrawState {
+    /// Whether a redraw was requested since the last frame.
+    requested: Cell<bool>,
+    /// Schedules the next frame of the render loop, once it has started.
+    schedule: RefCell<Option<Scheduler>>,
+}
+
+/// Schedules the next frame of a render loop.
+type Scheduler = Box<dyn Fn()>;
+
+/// Draws a frame of a render loop, clearing the terminal first if the flag is
+/// set, and returns what to do if drawing failed.
+type DrawCallback = dyn FnMut(bool) -> Result<(), RenderErrorAction>;
+
+/// Polls the gamepads of a render loop, returning whether any events were
+/// reported, or `None` if there is no gamepad handler.
+type PollCallback = dyn FnMut() -> Option<bool>;
+
+/// The `requestAnimationFrame` callback of a render loop.
+type FrameCallback = Closure<dyn FnMut(f64)>;
+
+impl std::fmt::Debug for RedrawState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedrawState")
+            .field("requested", &self.requested.get())
+            .finish()
+    }
+}
+
+impl RedrawHandle {
+    /// Constructs a new [`RedrawHandle`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Requests that the terminal is redrawn in the next animation frame.
+    pub fn request_redraw(&self) {
+        self.inner.requested.set(true);
+        if let Ok(schedule) = self.inner.schedule.try_borrow() {
+            if let Some(schedule) = schedule.as_ref() {
+                schedule();
+            }
+        }
+    }
+
+    /// Returns whether a redraw was requested, and resets the request.
+    fn take_request(&self) -> bool {
+        self.inner.requested.replace(false)
+    }
+}
+
+/// What to do after drawing a frame failed.
+///
+/// This is returned by the `on_error` hook of [`WebRenderer::try_draw_web`].
+///
+/// [`WebRenderer::try_draw_web`]: crate::WebRenderer::try_draw_web
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RenderErrorAction {
+    /// Keep the loop running, and draw the frame again in the next animation
+    /// frame.
+    Retry,
+    /// Pause the loop.
+    ///
+    /// It can be resumed with [`RenderLoop::resume`].
+    Stop,
+    /// Log the error to the browser console, show it in place of the
+    /// terminal's content, and pause the loop.
+    Show,
+}
+
+/// The targets of the input events that make the render loop draw a frame in
+/// on-demand mode.
+#[derive(Debug, Default)]
+pub(crate) struct InputTargets {
+    /// The element the terminal is drawn to.
+    pub element: Option<Element>,
+    /// The hidden element that receives IME composition.
+    pub ime_input: Option<Element>,
+    /// Whether key events are listened for on the whole document.
+    pub document_key_events: bool,
+}
+
+/// Options for the render loop.
+///
+/// See [`WebRenderer::draw_web_with_options`].
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use std::{cell::RefCell, rc::Rc, time::Duration};
+///
+/// use ratzilla::{ratatui::Terminal, RenderOptions, WebGl2Backend, WebRenderer};
+///
+/// let terminal = Terminal::new(WebGl2Backend::new()?)?;
+/// let position = Rc::new(RefCell::new(0.0));
+///
+/// let options = RenderOptions::new()
+///     .target_fps(30.0)
+///     .fixed_update(Duration::from_millis(10), {
+///         let position = position.clone();
+///         move |step| *position.borrow_mut() += step.as_secs_f64()
+///     });
+/// terminal
+///     .draw_web_with_options(options, move |frame| {
+///         // render the position...
+///     })
+///     .detach();
+/// # Ok(())
+/// # }
+/// ```
+///
+/// [`WebRenderer::draw_web_with_options`]: crate::WebRenderer::draw_web_with_options
+#[derive(Default)]
+pub struct RenderOptions {
+    /// Only draw frames when something changed.
+    on_demand: bool,
+    /// The handle for requesting frames.
+    redraw: RedrawHandle,
+    /// The maximum number of frames drawn per second.
+    target_fps: Option<f64>,
+    /// The fixed-timestep update callback.
+    fixed_update: Option<FixedUpdate>,
+    /// Keep drawing frames while the terminal is not visible.
+    render_when_hidden: bool,
+}
+
+impl std::fmt::Debug for RenderOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RenderOptions")
+            .field("on_demand", &self.on_demand)
+            .field("redraw", &self.redraw)
+            .field("target_fps", &self.target_fps)
+            .field("fixed_update", &self.fixed_update)
+            .field("render_when_hidden", &self.render_when_hidden)
+            .finish()
+    }
+}
+
+impl RenderOptions {
+    /// Constructs a new [`RenderOptions`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether frames are only drawn on demand.
+    ///
+    /// By default, a frame is drawn on every animation frame, i.e. at the
+    /// refresh rate of the display. In on-demand mode, a frame is only drawn
+    /// after an input event or a resize, or when requested with the
+    /// [`RedrawHandle`], which saves power on mostly static pages.
+    pub fn on_demand(mut self, on_demand: bool) -> Self {
+        self.on_demand = on_demand;
+        self
+    }
+
+    /// Sets the maximum number of frames drawn per second.
+    ///
+    /// By default, frames are drawn at the refresh rate of the display, which
+    /// may be 144 Hz or more. With a target frame rate, animation frames are
+    /// skipped until the frame interval has passed, so that e.g. a dashboard
+    /// that updates a few times a second can be drawn at 10 frames per second.
+    /// Values that aren't positive remove the cap.
+    pub fn target_fps(mut self, fps: f64) -> Self {
+        self.target_fps = (fps > 0.0).then_some(fps);
+        self
+    }
+
+    /// Sets a callback that is called at a fixed rate, separately from
+    /// rendering, e.g. to advance a simulation.
+    ///
+    /// Before each frame is drawn, the callback is called once for every
+    /// `step` that has passed since the previous frame, with `step` as its
+    /// argument. Simulations are therefore independent of the frame rate,
+    /// but only advance while frames are drawn.
+    pub fn fixed_update<F>(mut self, step: Duration, callback: F) -> Self
+    where
+        F: FnMut(Duration) + 'static,
+    {
+        self.fixed_update = Some(FixedUpdate {
+            timestep: FixedTimestep::new(step),
+            callback: Box::new(callback),
+        });
+        self
+    }
+
+    /// Sets whether frames are drawn while the terminal is not visible.
+    ///
+    /// By default, the render loop is suspended while the tab is hidden, or
+    /// while the terminal is scrolled out of view, e.g. when it is one of
+    /// several terminals on a page. When it becomes visible again, the whole
+    /// terminal is redrawn.
+    pub fn render_when_hidden(mut self, render_when_hidden: bool) -> Self {
+        self.render_when_hidden = render_when_hidden;
+        self
+    }
+
+    /// Returns a handle for requesting frames, e.g. from async tasks.
+    pub fn redraw_handle(&self) -> RedrawHandle {
+        self.redraw.clone()
+    }
+}
+
+/// Tracks whether the terminal is visible, to suspend the render loop while
+/// it is not.
+#[derive(Debug, Default)]
+struct Visibility {
+    /// The visibility state shared with the listeners.
+    state: Rc<VisibilityState>,
+    /// Tracks whether the page is hidden.
+    _visibility_listener: Option<EventCallback<web_sys::Event>>,
+    /// Tracks whether the terminal element is in view.
+    _intersection_callback: Option<IntersectionCallback>,
+}
+
+/// Whether the page and the terminal are visible.
+#[derive(Debug, Default)]
+struct VisibilityState {
+    /// Whether the page is hidden, e.g. in a background tab.
+    hidden: Cell<bool>,
+    /// Whether the terminal element is scrolled out of view.
+    offscreen: Cell<bool>,
+    /// Whether the whole terminal must be redrawn, as it was not visible.
+    full_redraw: Cell<bool>,
+}
+
+impl Visibility {
+    /// Starts tracking the visibility of the page and of the terminal
+    /// element, requesting a redraw when the terminal becomes visible again.
+    fn new(element: Option<Element>, redraw: &RedrawHandle) -> Self {
+        let state = Rc::new(VisibilityState::default());
+        let visibility_listener = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| {
+                state.hidden.set(document.hidden());
+                let state = state.clone();
+                let redraw = redraw.clone();
+                EventCallback::new(
+                    document.clone(),
+                    VISIBILITY_EVENT_TYPES,
+                    move |_: web_sys::Event| {
+                        state.update(&state.hidden, document.hidden(), &redraw)
+                    },
+                )
+                .ok()
+            });
+        let intersection_callback = element.and_then(|element| {
+            let state = state.clone();
+            let redraw = redraw.clone();
+            IntersectionCallback::new(&element, move |in_view| {
+                state.update(&state.offscreen, !in_view, &redraw)
+            })
+            .ok()
+        });
+        Self {
+            state,
+            _visibility_listener: visibility_listener,
+            _intersection_callback: intersection_callback,
+        }
+    }
+}
+
+impl VisibilityState {
+    /// Returns whether the terminal is visible.
+    fn is_visible(&self) -> bool {
+        !self.hidden.get() && !self.offscreen.get()
+    }
+
+    /// Sets one of the visibility flags, requesting a full redraw if the
+    /// terminal became visible again.
+    fn update(&self, flag: &Cell<bool>, value: bool, redraw: &RedrawHandle) {
+        let was_visible = self.is_visible();
+        flag.set(value);
+        if !was_visible && self.is_visible() {
+            self.full_redraw.set(true);
+            redraw.request_redraw();
+        }
+    }
+}
+
+/// Skips animation frames to draw at most at a target frame rate.
+#[derive(Debug)]
+struct FramePacer {
+    /// The minimum time between frames, in milliseconds.
+    interval: f64,
+    /// The time the last frame was due, in milliseconds.
+    last_frame: Option<f64>,
+}
+
+impl FramePacer {
+    /// Creates a new [`FramePacer`] for the given frame rate.
+    fn new(fps: f64) -> Self {
+        Self {
+            interval: 1000.0 / fps,
+            last_frame: None,
+        }
+    }
+
+    /// Returns whether a frame should be drawn at the given time.
+    fn ready(&mut self, now: f64) -> bool {
+        let Some(last_frame) = self.last_frame else {
+            self.last_frame = Some(now);
+            return true;
+        };
+        let elapsed = now - last_frame;
+        if elapsed < self.interval - FRAME_TIME_TOLERANCE {
+            return false;
+        }
+        // keep a steady cadence, unless frames were missed
+        self.last_frame = if elapsed < 2.0 * self.interval {
+            Some(last_frame + self.interval)
+        } else {
+            Some(now)
+        };
+        true
+    }
+}
+
+/// Counts the fixed-length steps that have passed between frames.
+#[derive(Debug)]
+struct FixedTimestep {
+    /// The length of a step, in milliseconds.
+    step: f64,
+    /// The time that has passed, but not been stepped through yet.
+    accumulator: f64,
+    /// The time of the last frame, in milliseconds.
+    last_frame: Option<f64>,
+}
+
+impl FixedTimestep {
+    /// Creates a new [`FixedTimestep`] with the given step length.
+    fn new(step: Duration) -> Self {
+        Self {
+            step: (step.as_secs_f64() * 1000.0).max(f64::EPSILON),
+            accumulator: 0.0,
+            last_frame: None,
+        }
+    }
+
+    /// Returns the number of steps that have passed up to the given time.
+    fn advance(&mut self, now: f64) -> u32 {
+        let elapsed = self.last_frame.map_or(0.0, |last| now - last);
+        self.last_frame = Some(now);
+        self.accumulator += elapsed.clamp(0.0, MAX_FIXED_UPDATE_TIME);
+        let steps = (self.accumulator / self.step).floor();
+        self.accumulator -= steps * self.step;
+        steps as u32
+    }
+}
+
+/// A fixed-timestep update callback.
+struct FixedUpdate {
+    /// The steps that have passed.
+    timestep: FixedTimestep,
+    /// The callback to call for every step.
+    callback: Box<dyn FnMut(Duration)>,
+}
+
+impl FixedUpdate {
+    /// Calls the callback for every step that has passed up to the given time.
+    fn run(&mut self, now: f64) {
+        let step = Duration::from_secs_f64(self.timestep.step / 1000.0);
+        for _ in 0..self.timestep.advance(now) {
+            (self.callback)(step);
+        }
+    }
+}
+
+impl std::fmt::Debug for FixedUpdate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FixedUpdate")
+            .field("timestep", &self.timestep)
+            .finish()
+    }
+}
+
+/// A handle to a running render loop, returned by [`WebRenderer::start_web`].
+///
+/// The loop owns the terminal while it is running. It can be paused and
+/// resumed, and stopped to get the terminal back, e.g. to draw a different
+/// screen or to tear down the terminal when navigating away from a page of a
+/// single-page application:
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// use ratzilla::{ratatui::Terminal, DomBackend, WebRenderer};
+///
+/// let terminal = Terminal::new(DomBackend::new()?)?;
+/// let render_loop = terminal.start_web(|frame| {
+///     // render the first screen...
+/// });
+///
+/// // later, e.g. in an event handler
+/// let terminal = render_loop.stop();
+/// terminal.draw_web(|frame| {
+///     // render the second screen...
+/// });
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Dropping the handle stops the loop, and drops the terminal along with its
+/// event handlers. Use [`RenderLoop::detach`] to keep the loop running for
+/// the lifetime of the page instead.
+///
+/// [`WebRenderer::start_web`]: crate::WebRenderer::start_web
+#[must_use = "the render loop stops when the handle is dropped, use `detach` to keep it running"]
+pub struct RenderLoop<R> {
+    /// The state shared with the animation frame callback.
+    state: Rc<LoopState>,
+    /// The terminal, until the loop is stopped.
+    renderer: Rc<RefCell<Option<R>>>,
+    /// Returns the targets of the terminal's input events.
+    input: Box<dyn Fn(&R) -> InputTargets>,
+}
+
+/// The state of a [`RenderLoop`].
+struct LoopState {
+    /// Whether frames are only drawn on demand.
+    on_demand: bool,
+    /// The handle for requesting frames.
+    redraw: RedrawHandle,
+    /// Draws a frame, returning what to do if drawing failed.
+    draw: RefCell<Box<DrawCallback>>,
+    /// Polls the gamepads.
+    poll: RefCell<Box<PollCallback>>,
+    /// Whether a gamepad handler is set, which keeps the animation frames
+    /// running even while no frames are drawn.
+    gamepads: Cell<bool>,
+    /// The animation frame callback.
+    closure: RefCell<Option<FrameCallback>>,
+    /// The ID of the requested animation frame, if one is pending.
+    frame_id: Cell<Option<i32>>,
+    /// Whether the loop is paused.
+    paused: Cell<bool>,
+    /// Whether the terminal is visible.
+    visibility: Visibility,
+    /// Caps the frame rate, if a target frame rate is set.
+    pacer: RefCell<Option<FramePacer>>,
+    /// The fixed-timestep update callback.
+    fixed_update: RefCell<Option<FixedUpdate>>,
+    /// Requests frames on input events in on-demand mode.
+    input_listeners: RefCell<Vec<EventCallback<web_sys::Event>>>,
+}
+
+impl<R: 'static> RenderLoop<R> {
+    /// Starts a render loop that calls `draw` with the renderer to draw each
+    /// frame.
+    ///
+    /// `draw` is also told whether to clear the terminal first, so that the
+    /// whole terminal is redrawn after it was not visible. If drawing fails,
+    /// it returns the action to take. The loop is suspended while the
+    /// terminal element returned by `input` is out of view, and in on-demand
+    /// mode, frames are drawn after input events on the returned targets.
+    /// The first frame is always drawn.
+    ///
+    /// `poll` is called on every animation frame, whether or not a frame is
+    /// drawn, and returns whether there were any gamepad events, or `None` if
+    /// there is no gamepad handler.
+    pub(crate) fn start<I, P, F>(
+        options: RenderOptions,
+        renderer: R,
+        input: I,
+        mut poll: P,
+        mut draw: F,
+    ) -> Self
+    where
+        I: Fn(&R) -> InputTargets + 'static,
+        P: FnMut(&mut R) -> Option<bool> + 'static,
+        F: FnMut(&mut R, bool) -> Result<(), RenderErrorAction> + 'static,
+    {
+        let targets = input(&renderer);
+        let element = targets.element.clone();
+        let renderer = Rc::new(RefCell::new(Some(renderer)));
+        let draw = {
+            let renderer = renderer.clone();
+            move |clear| match renderer.try_borrow_mut() {
+                Ok(mut renderer) => renderer
+                    .as_mut()
+                    .map_or(Ok(()), |renderer| draw(renderer, clear)),
+                Err(_) => Ok(()),
+            }
+        };
+        let poll = {
+            let renderer = renderer.clone();
+            move || match renderer.try_borrow_mut() {
+                Ok(mut renderer) => renderer.as_mut().and_then(&mut poll),
+                Err(_) => None,
+            }
+        };
+        let visibility = if options.render_when_hidden {
+            Visibility::default()
+        } else {
+            Visibility::new(element, &options.redraw)
+        };
+
+        let state = Rc::new(LoopState {
+            on_demand: options.on_demand,
+            redraw: options.redraw,
+            draw: RefCell::new(Box::new(draw)),
+            poll: RefCell::new(Box::new(poll)),
+            gamepads: Cell::new(false),
+            closure: RefCell::new(None),
+            frame_id: Cell::new(None),
+            paused: Cell::new(false),
+            visibility,
+            pacer: RefCell::new(options.target_fps.map(FramePacer::new)),
+            fixed_update: RefCell::new(options.fixed_update),
+            input_listeners: RefCell::new(Vec::new()),
+        });
+        state.listen(targets);
+
+        let weak = Rc::downgrade(&state);
+        *state.closure.borrow_mut() = Some(Closure::new(move |timestamp: f64| {
+            if let Some(state) = weak.upgrade() {
+                state.frame(timestamp);
+            }
+        }));
+
+        let weak = Rc::downgrade(&state);
+        *state.redraw.inner.schedule.borrow_mut() = Some(Box::new(move || {
+            if let Some(state) = weak.upgrade() {
+                state.schedule();
+            }
+        }));
+
+        state.redraw.request_redraw();
+        Self {
+            state,
+            renderer,
+            input: Box::new(input),
+        }
+    }
+
+    /// Pauses the loop, so that no frames are drawn until it is resumed.
+    ///
+    /// Gamepads are still polled while the loop is paused.
+    pub fn pause(&self) {
+        self.state.paused.set(true);
+        if !self.state.gamepads.get() {
+            self.state.cancel();
+        }
+    }
+
+    /// Resumes the loop after it was paused, and draws a frame.
+    pub fn resume(&self) {
+        self.state.paused.set(false);
+        self.state.redraw.request_redraw();
+    }
+
+    /// Returns whether the loop is paused.
+    pub fn is_paused(&self) -> bool {
+        self.state.paused.get()
+    }
+
+    /// Returns a handle for requesting frames.
+    ///
+    /// See [`RedrawHandle`].
+    pub fn redraw_handle(&self) -> RedrawHandle {
+        self.state.redraw.clone()
+    }
+
+    /// Calls a function with the terminal, e.g. to set up event handlers
+    /// after the loop has started, and draws a frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within the render callback.
+    pub fn with_terminal<T>(&self, f: impl FnOnce(&mut R) -> T) -> T {
+        let result = {
+            let mut renderer = self.renderer.borrow_mut();
+            let renderer = renderer
+                .as_mut()
+                .expect("the terminal is only taken by stop");
+            let result = f(renderer);
+            // the handlers may have changed the targets of input events
+            self.state.listen((self.input)(renderer));
+            result
+        };
+        // wake the loop, so that a gamepad handler that was set is polled
+        self.state.gamepads.set(true);
+        self.state.redraw.request_redraw();
+        result
+    }
+
+    /// Stops the loop and returns the terminal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called from within the render callback.
+    pub fn stop(self) -> R {
+        self.state.cancel();
+        let renderer = self.renderer.borrow_mut().take();
+        renderer.expect("the terminal is only taken by stop")
+    }
+
+    /// Keeps the loop running for the lifetime of the page, without a
+    /// handle to control it.
+    pub fn detach(self) {
+        std::mem::forget(self);
+    }
+}
+
+impl<R> Drop for RenderLoop<R> {
+    fn drop(&mut self) {
+        self.state.cancel();
+        *self.state.redraw.inner.schedule.borrow_mut() = None;
+    }
+}
+
+impl<R> std::fmt::Debug for RenderLoop<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RenderLoop")
+            .field("on_demand", &self.state.on_demand)
+            .field("paused", &self.state.paused.get())
+            .field("redraw", &self.state.redraw)
+            .finish()
+    }
+}
+
+impl LoopState {
+    /// Requests an animation frame, unless one is already pending, or the
+    /// loop is paused or the terminal is not visible and there are no
+    /// gamepads to poll.
+    fn schedule(&self) {
+        if self.frame_id.get().is_some() {
+            return;
+        }
+        if (self.paused.get() || !self.visibility.state.is_visible()) && !self.gamepads.get() {
+            return;
+        }
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        if let Some(closure) = self.closure.borrow().as_ref() {
+            let id = window.request_animation_frame(closure.as_ref().unchecked_ref());
+            self.frame_id.set(id.ok());
+        }
+    }
+
+    /// Requests a frame on input events on the given targets, if frames are
+    /// only drawn on demand.
+    ///
+    /// Without a terminal element, its events are listened for on the window.
+    fn listen(&self, targets: InputTargets) {
+        if !self.on_demand {
+            return;
+        }
+        let window = web_sys::window();
+        let document = window.as_ref().and_then(web_sys::Window::document);
+        let element = targets.element.map(EventTarget::from);
+        let listeners: [(Option<EventTarget>, &'static [&'static str]); 4] = [
+            (window.clone().map(Into::into), WINDOW_EVENT_TYPES),
+            (
+                element.or_else(|| window.map(Into::into)),
+                TERMINAL_EVENT_TYPES,
+            ),
+            (targets.ime_input.map(Into::into), IME_EVENT_TYPES),
+            (
+                document
+                    .filter(|_| targets.document_key_events)
+                    .map(Into::into),
+                DOCUMENT_EVENT_TYPES,
+            ),
+        ];
+        *self.input_listeners.borrow_mut() = listeners
+            .into_iter()
+            .filter_map(|(target, event_types)| {
+                let redraw = self.redraw.clone();
+                EventCallback::new(target?, event_types, move |_| redraw.request_redraw()).ok()
+            })
+            .collect();
+    }
+
+    /// Cancels the pending animation frame, if any.
+    fn cancel(&self) {
+        if let (Some(id), Some(window)) = (self.frame_id.take(), web_sys::window()) {
+            let _ = window.cancel_animation_frame(id);
+        }
+    }
+
+    /// Polls the gamepads, draws a frame if needed, and schedules the next
+    /// animation frame.
+    fn frame(&self, timestamp: f64) {
+        self.frame_id.set(None);
+        let polled = match self.poll.try_borrow_mut() {
+            Ok(mut poll) => poll(),
+            Err(_) => Some(false),
+        };
+        self.gamepads.set(polled.is_some());
+        if polled == Some(true) {
+            self.redraw.inner.requested.set(true);
+        }
+        if self.draw_frame(timestamp) || self.gamepads.get() {
+            self.schedule();
+        }
+    }
+
+    /// Draws a frame if needed, and returns whether another animation frame
+    /// is needed to draw the next one.
+    fn draw_frame(&self, timestamp: f64) -> bool {
+        if self.paused.get() || !self.visibility.state.is_visible() {
+            return false;
+        }
+        if self.on_demand && !self.redraw.inner.requested.get() {
+            return false;
+        }
+        let ready = match self.pacer.borrow_mut().as_mut() {
+            Some(pacer) => pacer.ready(timestamp),
+            None => true,
+        };
+        if !ready {
+            return true;
+        }
+
+        self.redraw.take_request();
+        if let Ok(mut fixed_update) = self.fixed_update.try_borrow_mut() {
+            if let Some(fixed_update) = fixed_update.as_mut() {
+                fixed_update.run(timestamp);
+            }
+        }
+        let full_redraw = self.visibility.state.full_redraw.take();
+        let result = match self.draw.try_borrow_mut() {
+            Ok(mut draw) => draw(full_redraw),
+            Err(_) => Ok(()),
+        };
+        match result {
+            Ok(()) => !self.on_demand,
+            Err(RenderErrorAction::Retry) => {
+                self.visibility.state.full_redraw.set(full_redraw);
+                self.redraw.inner.requested.set(true);
+                true
+            }
+            Err(RenderErrorAction::Stop | RenderErrorAction::Show) => {
+                self.paused.set(true);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_pacer() {
+        // 30 fps on a 120 Hz display
+        let mut pacer = FramePacer::new(30.0);
+        let drawn: Vec<bool> = (0..9)
+            .map(|frame| pacer.ready(frame as f64 * 1000.0 / 120.0))
+            .collect();
+        assert_eq!(
+            drawn,
+            [true, false, false, false, true, false, false, false, true]
+        );
+
+        // jitter doesn't skip frames
+        let mut pacer = FramePacer::new(60.0);
+        assert!(pacer.ready(0.0));
+        assert!(pacer.ready(16.2));
+        assert!(pacer.ready(33.5));
+        assert!(!pacer.ready(40.0));
+
+        // missed frames don't cause a burst of frames
+        assert!(pacer.ready(1000.0));
+        assert!(!pacer.ready(1010.0));
+    }
+
+    #[test]
+    fn test_fixed_timestep() {
+        let mut timestep = FixedTimestep::new(Duration::from_millis(10));
+        assert_eq!(timestep.advance(100.0), 0);
+        assert_eq!(timestep.advance(116.0), 1);
+        assert_eq!(timestep.advance(132.0), 2);
+        assert_eq!(timestep.advance(135.0), 0);
+
+        // long pauses are only partially caught up on
+        assert_eq!(timestep.advance(10_000.0), 25);
+    }
+
+    #[test]
+    fn test_visibility_state() {
+        let redraw = RedrawHandle::new();
+        let state = VisibilityState::default();
+        assert!(state.is_visible());
+
+        // hiding the terminal doesn't redraw it
+        state.update(&state.offscreen, true, &redraw);
+        assert!(!state.is_visible());
+        assert!(!redraw.take_request());
+
+        // it is only redrawn once both the page and the terminal are visible
+        state.update(&state.hidden, true, &redraw);
+        state.update(&state.offscreen, false, &redraw);
+        assert!(!state.is_visible());
+        assert!(!state.full_redraw.get());
+        state.update(&state.hidden, false, &redraw);
+        assert!(state.is_visible());
+        assert!(state.full_redraw.get());
+        assert!(redraw.take_request());
+    }
+}